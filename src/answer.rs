@@ -0,0 +1,230 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::cli::Part;
+
+/// The answer to a single part of a day's challenge.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    /// Multi-line answers, such as letters drawn on a grid.
+    Lines(Vec<String>),
+}
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Lines(lines) if lines.len() > 1)
+    }
+}
+
+/// Why a part could not be solved. An input that parses can still break the
+/// assumptions a solution relies on, such as there being a way to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError(String);
+impl SolveError {
+    pub fn new(reason: impl fmt::Display) -> Self {
+        SolveError(reason.to_string())
+    }
+}
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        // Integers are compared by value, regardless of their signedness.
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(u), Answer::Signed(s)) | (Answer::Signed(s), Answer::Unsigned(u)) => {
+                i128::from(*u) == i128::from(*s)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Lines(a), Answer::Lines(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Unsigned(n)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Unsigned(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Unsigned(n as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Signed(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Signed(n.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Lines(lines)
+    }
+}
+
+/// Known answers per day and part, as stored in an `answers.toml` file.
+///
+/// Only a small subset of TOML is supported: `[dayNN]` tables containing
+/// `part1`/`part2` keys, with integer, string or string array values.
+#[derive(Debug, Default)]
+pub struct AnswerSheet {
+    answers: BTreeMap<(usize, Part), Answer>,
+}
+impl AnswerSheet {
+    pub fn parse(text: &str) -> Result<AnswerSheet, AnswerSheetError> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let error = |message: &str| AnswerSheetError {
+                line: idx + 1,
+                message: message.to_owned(),
+            };
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let day_no = table
+                    .strip_prefix("day")
+                    .and_then(|d| d.parse().ok())
+                    .ok_or_else(|| error("expected a table named [dayNN]"))?;
+                day = Some(day_no);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected a 'key = value' pair"))?;
+            let day = day.ok_or_else(|| error("answers must be placed in a [dayNN] table"))?;
+            let part = match key.trim() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(error("expected key 'part1' or 'part2'")),
+            };
+            let answer = parse_value(value.trim()).ok_or_else(|| error("invalid answer"))?;
+
+            if answers.insert((day, part), answer).is_some() {
+                return Err(error("duplicate answer"));
+            }
+        }
+
+        Ok(AnswerSheet { answers })
+    }
+
+    pub fn get(&self, day_no: usize, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day_no, part))
+    }
+}
+
+fn parse_value(value: &str) -> Option<Answer> {
+    if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        // Strings may contain commas themselves, so each one has to be read in
+        // full before looking for the comma that separates it from the next.
+        let mut lines = vec![];
+        let mut rest = items.trim_start();
+        while !rest.is_empty() {
+            let (line, after) = take_string(rest)?;
+            lines.push(line);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after.trim_start();
+            } else if !rest.is_empty() {
+                return None;
+            }
+        }
+        Some(Answer::Lines(lines))
+    } else if value.starts_with('"') {
+        parse_string(value).map(Answer::Text)
+    } else if value.starts_with('-') {
+        value.parse().ok().map(Answer::Signed)
+    } else {
+        value.parse().ok().map(Answer::Unsigned)
+    }
+}
+
+fn parse_string(value: &str) -> Option<String> {
+    match take_string(value)? {
+        (result, "") => Some(result),
+        _ => None,
+    }
+}
+
+/// Reads a quoted string from the start of `value`, returning its unescaped
+/// content and whatever follows the closing quote.
+fn take_string(value: &str) -> Option<(String, &str)> {
+    let content = value.strip_prefix('"')?;
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next()?.1 {
+                'n' => result.push('\n'),
+                c @ ('"' | '\\') => result.push(c),
+                _ => return None,
+            },
+            '"' => return Some((result, &content[idx + 1..])),
+            c => result.push(c),
+        }
+    }
+    None
+}
+
+#[derive(Debug)]
+pub struct AnswerSheetError {
+    pub line: usize,
+    pub message: String,
+}
+impl fmt::Display for AnswerSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
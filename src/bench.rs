@@ -0,0 +1,288 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    hint::black_box,
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    input::{DayInput, InputSource, LoadError},
+    measure, Day,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub samples: usize,
+    pub warmup: usize,
+}
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            samples: 100,
+            warmup: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.into_iter().find(|p| p.name() == name)
+    }
+}
+
+pub struct DayBench {
+    pub day_no: usize,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+/// Repeatedly parses the input for a day and solves both parts, measuring each
+/// phase separately.
+pub fn bench_day<D: Day>(inputs: &InputSource, config: BenchConfig) -> Result<DayBench, LoadError> {
+    let raw_input = inputs.read_day(D::DAY_NO)?;
+    // Make sure the input is valid before starting the measurements.
+    D::Input::load(raw_input).map_err(|e| e.for_day(D::DAY_NO))?;
+
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for run in 0..config.warmup + config.samples {
+        let (input, t_parse) = measure(|| D::Input::load(black_box(raw_input)));
+        let input = input.unwrap();
+        let (_, t_1) = measure(|| black_box(D::solve_challenge_1(black_box(&input))));
+        let (_, t_2) = measure(|| black_box(D::solve_challenge_2(black_box(&input))));
+
+        if run >= config.warmup {
+            for (phase_samples, t) in samples.iter_mut().zip([t_parse, t_1, t_2]) {
+                phase_samples.push(t);
+            }
+        }
+    }
+
+    Ok(DayBench {
+        day_no: D::DAY_NO,
+        phases: Phase::ALL
+            .into_iter()
+            .zip(samples)
+            .map(|(phase, mut s)| (phase, Stats::from_samples(&mut s)))
+            .collect(),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+impl Stats {
+    /// Summarises the samples. The samples must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+
+        let count = samples.len() as f64;
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Stats {
+            min: samples[0],
+            median: percentile(samples, 50),
+            mean: Duration::from_secs_f64(mean),
+            p95: percentile(samples, 95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Finds the nearest-rank percentile in a sorted list of samples.
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+    let rank = (percentile * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// Formats a duration in microseconds, right-aligned to the requested width.
+pub struct Micros(pub Duration);
+impl fmt::Display for Micros {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let micros = format!("{:.2}µs", self.0.as_secs_f64() * 1_000_000.0);
+        f.pad(&micros)
+    }
+}
+
+pub fn print_header() {
+    println!(
+        "{:>3}  {:<6} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "day", "phase", "min", "median", "mean", "p95", "stddev"
+    );
+}
+
+pub fn print_day(bench: &DayBench) {
+    for (phase, stats) in &bench.phases {
+        println!(
+            "{:>3}  {:<6} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            bench.day_no,
+            phase.name(),
+            Micros(stats.min),
+            Micros(stats.median),
+            Micros(stats.mean),
+            Micros(stats.p95),
+            Micros(stats.stddev),
+        );
+    }
+}
+
+/// Benchmark results of an earlier run, to compare new results against.
+pub struct Baseline {
+    stats: BTreeMap<(usize, Phase), Stats>,
+}
+impl Baseline {
+    const HEADER: &'static str = "day,phase,min_ns,median_ns,mean_ns,p95_ns,stddev_ns";
+
+    pub fn from_results(results: &[DayBench]) -> Baseline {
+        let stats = results
+            .iter()
+            .flat_map(|r| r.phases.iter().map(|&(phase, s)| ((r.day_no, phase), s)))
+            .collect();
+
+        Baseline { stats }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    fn parse(text: &str) -> io::Result<Baseline> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, l)| l) != Some(Self::HEADER) {
+            return Err(invalid_data(1, "unexpected header"));
+        }
+
+        let mut stats = BTreeMap::new();
+        for (idx, line) in lines.filter(|(_, l)| !l.is_empty()) {
+            let fields: Vec<_> = line.split(',').collect();
+            let [day, phase, min, median, mean, p95, stddev] = fields[..] else {
+                return Err(invalid_data(idx + 1, "expected 7 fields"));
+            };
+            let day_no = day
+                .parse()
+                .map_err(|_| invalid_data(idx + 1, "invalid day"))?;
+            let phase =
+                Phase::from_name(phase).ok_or_else(|| invalid_data(idx + 1, "invalid phase"))?;
+            let nanos = |field: &str| {
+                field
+                    .parse()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| invalid_data(idx + 1, "invalid duration"))
+            };
+
+            let s = Stats {
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                p95: nanos(p95)?,
+                stddev: nanos(stddev)?,
+            };
+            stats.insert((day_no, phase), s);
+        }
+
+        Ok(Baseline { stats })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut w = io::BufWriter::new(fs::File::create(path)?);
+        writeln!(w, "{}", Self::HEADER)?;
+        for (&(day_no, phase), s) in &self.stats {
+            writeln!(
+                w,
+                "{day_no},{},{},{},{},{},{}",
+                phase.name(),
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.p95.as_nanos(),
+                s.stddev.as_nanos()
+            )?;
+        }
+        w.flush()
+    }
+
+    /// Compares the medians of new results against the baseline. Phases which
+    /// are missing from the baseline are left out.
+    pub fn compare(&self, results: &[DayBench], threshold_percent: u32) -> Vec<Comparison> {
+        results
+            .iter()
+            .flat_map(|r| r.phases.iter().map(move |&(phase, s)| (r.day_no, phase, s)))
+            .filter_map(|(day_no, phase, current)| {
+                let baseline = self.stats.get(&(day_no, phase))?;
+                let change = current.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0;
+
+                Some(Comparison {
+                    day_no,
+                    phase,
+                    baseline: baseline.median,
+                    current: current.median,
+                    change,
+                    regressed: change * 100.0 > threshold_percent as f64,
+                })
+            })
+            .collect()
+    }
+}
+
+fn invalid_data(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {line}: {message}"),
+    )
+}
+
+pub struct Comparison {
+    pub day_no: usize,
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change of the median, where 0.1 means 10% slower.
+    pub change: f64,
+    pub regressed: bool,
+}
+
+pub fn print_comparison(comparisons: &[Comparison], threshold_percent: u32) {
+    println!();
+    println!("comparison of medians against the baseline (threshold: {threshold_percent}%)");
+    println!(
+        "{:>3}  {:<6} {:>12} {:>12} {:>9}",
+        "day", "phase", "baseline", "current", "change"
+    );
+    for c in comparisons {
+        println!(
+            "{:>3}  {:<6} {:>12} {:>12} {:>+8.1}%{}",
+            c.day_no,
+            c.phase.name(),
+            Micros(c.baseline),
+            Micros(c.current),
+            c.change * 100.0,
+            if c.regressed { "  REGRESSED" } else { "" }
+        );
+    }
+}
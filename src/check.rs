@@ -0,0 +1,70 @@
+use crate::{
+    answer::{Answer, AnswerSheet, SolveError},
+    cli::Part,
+    Solution,
+};
+
+pub enum Verdict<'s> {
+    Pass,
+    Fail {
+        expected: &'s Answer,
+        got: &'s Answer,
+    },
+    Unknown {
+        got: &'s Answer,
+    },
+    /// The part could not be solved, so there is no answer to compare.
+    Error(&'s SolveError),
+}
+
+pub struct PartCheck<'s> {
+    pub part: Part,
+    pub verdict: Verdict<'s>,
+}
+
+/// Compares the answers in a solution against the known answers.
+pub fn check_solution<'s>(sheet: &'s AnswerSheet, solution: &'s Solution) -> Vec<PartCheck<'s>> {
+    solution
+        .parts()
+        .map(|(part, p)| {
+            let verdict = match (&p.answer, sheet.get(solution.day_no, part)) {
+                (Err(e), _) => Verdict::Error(e),
+                (Ok(got), Some(expected)) if expected == got => Verdict::Pass,
+                (Ok(got), Some(expected)) => Verdict::Fail { expected, got },
+                (Ok(got), None) => Verdict::Unknown { got },
+            };
+
+            PartCheck { part, verdict }
+        })
+        .collect()
+}
+
+pub fn all_passed(sheet: &AnswerSheet, solution: &Solution) -> bool {
+    check_solution(sheet, solution)
+        .iter()
+        .all(|c| !matches!(c.verdict, Verdict::Fail { .. } | Verdict::Error(_)))
+}
+
+/// Prints the result of checking a solution, returning whether none of the
+/// answers were wrong or missing.
+pub fn print_check(sheet: &AnswerSheet, solution: &Solution) -> bool {
+    println!("Day {}:", solution.day_no);
+
+    let mut passed = true;
+    for check in check_solution(sheet, solution) {
+        let part_no = check.part.number();
+        match check.verdict {
+            Verdict::Pass => println!(" - part {part_no}: pass"),
+            Verdict::Fail { expected, got } => {
+                passed = false;
+                println!(" - part {part_no}: FAIL (expected {expected}, got {got})");
+            }
+            Verdict::Unknown { got } => println!(" - part {part_no}: unknown (got {got})"),
+            Verdict::Error(e) => {
+                passed = false;
+                println!(" - part {part_no}: FAIL ({e})");
+            }
+        }
+    }
+    passed
+}
@@ -0,0 +1,413 @@
+use std::{fmt, ops::RangeInclusive, path::PathBuf};
+
+use crate::{bench::BenchConfig, common::interval::Interval, report::Format};
+
+pub const USAGE: &str = "\
+Usage: aoc [COMMAND] [OPTIONS] [DAYS...]
+
+Solves the selected days, or all of them if no days are given.
+
+Commands:
+  bench       benchmark parsing and solving of the selected days
+  fetch       download the inputs of the selected days into the input directory,
+              skipping those that are already present. The inputs are requested
+              from $AOC_BASE_URL/day/<N>/input (plain http:// only), using the
+              session cookie in $AOC_SESSION
+  graph       print the day 8 network in Graphviz DOT format, to be rendered
+              with e.g. `dot -Tsvg`
+
+Days:
+  5           a single day
+  3..=8       an inclusive range of days
+  3..8        an exclusive range of days
+
+Options:
+  --all         solve all available days
+  --part <N>    only solve part 1 or part 2
+  --check       compare the answers against those stored in answers.toml
+  --parallel    solve the days concurrently
+  --parallel-parts
+                like --parallel, but also solve both parts of a day concurrently
+  --format <FORMAT>
+                output format for answers: text, json or csv (default: text)
+  --input-dir <DIR>
+                read inputs from DIR (default: $AOC_INPUT_DIR, or src/input)
+  --trace <SEEDS>
+                instead of solving, show how a seed (79) or a range of seeds
+                (79..93) maps to each category of day 5
+  --seeds-for <LOCATIONS>
+                instead of solving, show which seeds of day 5 are planted in a
+                location (35) or a range of locations (35..50)
+  --visualize   instead of solving, draw the pipes of day 10, highlighting the
+                main loop and marking the tiles it encloses
+  -h, --help    print this help text
+
+Benchmark options:
+  --samples <N> number of measured runs per day (default: 100)
+  --warmup <N>  number of unmeasured runs preceding the measurements (default: 10)
+  --save-baseline <FILE>
+                store the results as a baseline for later comparisons
+  --baseline <FILE>
+                compare the results against a previously saved baseline, failing
+                if any median regressed by more than the threshold
+  --threshold <PERCENT>
+                allowed slowdown relative to the baseline (default: 10)
+
+Graph options:
+  --cycles      colour the cycle each ghost ends up in";
+
+/// Options that only affect how days are solved.
+const SOLVE_OPTIONS: [&str; 5] = [
+    "--part",
+    "--check",
+    "--parallel",
+    "--parallel-parts",
+    "--format",
+];
+
+/// Options that may only be passed to a specific command.
+const COMMAND_OPTIONS: [(&str, &str); 6] = [
+    ("--samples", "bench"),
+    ("--warmup", "bench"),
+    ("--save-baseline", "bench"),
+    ("--baseline", "bench"),
+    ("--threshold", "bench"),
+    ("--cycles", "graph"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+impl Part {
+    pub fn number(&self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    /// The ranges of days as given, which may overlap or contain days there is
+    /// no solution for.
+    Days(Vec<RangeInclusive<usize>>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Solve,
+    Bench(BenchOptions),
+    Fetch,
+    /// Traces seeds through the day 5 almanac.
+    Trace(Interval),
+    /// Finds the seeds planted in a range of locations in the day 5 almanac.
+    SeedsFor(Interval),
+    /// Prints the day 8 network as a Graphviz graph.
+    Graph {
+        cycles: bool,
+    },
+    /// Draws the day 10 pipe map.
+    Visualize,
+}
+
+impl Command {
+    /// The day a command is about, for commands which only work on one day,
+    /// along with the option or command to blame for it.
+    fn only_day(&self) -> Option<(&'static str, usize)> {
+        match self {
+            Command::Trace(_) => Some(("--trace", 5)),
+            Command::SeedsFor(_) => Some(("--seeds-for", 5)),
+            Command::Graph { .. } => Some(("graph", 8)),
+            Command::Visualize => Some(("--visualize", 10)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub config: BenchConfig,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    /// Percentage by which a phase may be slower than its baseline.
+    pub threshold: u32,
+}
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            config: Default::default(),
+            baseline: None,
+            save_baseline: None,
+            threshold: 10,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub command: Command,
+    pub selection: Selection,
+    pub part: Option<Part>,
+    pub check: bool,
+    pub parallel: bool,
+    pub parallel_parts: bool,
+    pub format: Format,
+    pub input_dir: Option<PathBuf>,
+    pub help: bool,
+}
+impl Options {
+    pub fn from_env() -> Result<Options, CliError> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+        let mut subcommand = None;
+        let mut all = false;
+        let mut days = vec![];
+        let mut part = None;
+        let mut check = false;
+        let mut parallel = false;
+        let mut parallel_parts = false;
+        let mut format = Format::Text;
+        let mut input_dir = None;
+        let mut trace = None;
+        let mut seeds_for = None;
+        let mut visualize = false;
+        let mut bench = BenchOptions::default();
+        let mut cycles = false;
+        let mut command_only = None;
+        let mut solve_only = None;
+        let mut help = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Options taking a value accept both `--option value` and `--option=value`.
+            let (name, mut inline_value) = match arg.split_once('=') {
+                Some((name, value)) if arg.starts_with("--") => (name, Some(value.to_owned())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |option: &'static str| {
+                inline_value
+                    .take()
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue(option))
+            };
+
+            match name {
+                "--all" => all = true,
+                "--check" => check = true,
+                "--parallel" => parallel = true,
+                "--parallel-parts" => {
+                    parallel = true;
+                    parallel_parts = true;
+                }
+                "--cycles" => cycles = true,
+                "--visualize" => visualize = true,
+                "-h" | "--help" => help = true,
+                "--part" => part = Some(parse_part(&value("--part")?)?),
+                "--format" => format = parse_format(&value("--format")?)?,
+                "--input-dir" => input_dir = Some(PathBuf::from(value("--input-dir")?)),
+                "--trace" => trace = Some(parse_interval("--trace", &value("--trace")?)?),
+                "--seeds-for" => {
+                    seeds_for = Some(parse_interval("--seeds-for", &value("--seeds-for")?)?)
+                }
+                "--samples" => {
+                    bench.config.samples = parse_count("--samples", &value("--samples")?)?
+                }
+                "--warmup" => bench.config.warmup = parse_count("--warmup", &value("--warmup")?)?,
+                "--save-baseline" => {
+                    bench.save_baseline = Some(PathBuf::from(value("--save-baseline")?))
+                }
+                "--baseline" => bench.baseline = Some(PathBuf::from(value("--baseline")?)),
+                "--threshold" => {
+                    let threshold = value("--threshold")?;
+                    bench.threshold = threshold
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| CliError::InvalidValue("--threshold", threshold))?;
+                }
+                "bench" if subcommand.is_none() && days.is_empty() => subcommand = Some("bench"),
+                "fetch" if subcommand.is_none() && days.is_empty() => subcommand = Some("fetch"),
+                "graph" if subcommand.is_none() && days.is_empty() => subcommand = Some("graph"),
+                a if a.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+                a => days.push(parse_days(a)?),
+            }
+
+            if inline_value.is_some() {
+                return Err(CliError::UnexpectedValue(name.to_owned()));
+            }
+            command_only = command_only.or(COMMAND_OPTIONS.into_iter().find(|&(o, _)| o == name));
+            solve_only = solve_only.or(SOLVE_OPTIONS.into_iter().find(|&o| o == name));
+        }
+
+        // These options replace solving with something else entirely.
+        let mut replacements = vec![];
+        if let Some(seeds) = trace {
+            replacements.push(("--trace", Command::Trace(seeds)));
+        }
+        if let Some(locations) = seeds_for {
+            replacements.push(("--seeds-for", Command::SeedsFor(locations)));
+        }
+        if visualize {
+            replacements.push(("--visualize", Command::Visualize));
+        }
+        if let [(a, _), (b, _), ..] = replacements[..] {
+            return Err(CliError::Conflicting(a, b));
+        }
+        let replacement = replacements.pop();
+
+        let command = match (subcommand, command_only, replacement) {
+            (Some(command), _, Some((option, _))) => {
+                return Err(CliError::Conflicting(option, command))
+            }
+            (_, Some((option, command)), _) if subcommand != Some(command) => {
+                return Err(CliError::CommandOnly(option, command))
+            }
+            (Some("bench"), _, None) => Command::Bench(bench),
+            (Some("graph"), _, None) => Command::Graph { cycles },
+            (Some(_), _, None) => Command::Fetch,
+            (None, _, Some((_, command))) => command,
+            (None, _, None) => Command::Solve,
+        };
+
+        let selection = match (all, days.is_empty()) {
+            (true, false) => return Err(CliError::AllWithDays),
+            (_, true) => Selection::All,
+            (false, false) => Selection::Days(days),
+        };
+
+        // Leaving out the day is fine, but any other day is a mistake. As these
+        // commands don't solve anything, neither are options about solving.
+        if let Some((name, day)) = command.only_day() {
+            if all
+                || matches!(&selection, Selection::Days(days) if days.iter().any(|d| *d != (day..=day)))
+            {
+                return Err(CliError::OnlyDay(name, day));
+            }
+            if let Some(option) = solve_only {
+                return Err(CliError::Conflicting(option, name));
+            }
+        }
+
+        // Benchmarking and fetching don't solve anything either, whatever the days.
+        if let (Some(option), Some(command @ ("bench" | "fetch"))) = (solve_only, subcommand) {
+            return Err(CliError::Conflicting(option, command));
+        }
+
+        Ok(Options {
+            command,
+            selection,
+            part,
+            check,
+            parallel,
+            parallel_parts,
+            format,
+            input_dir,
+            help,
+        })
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, CliError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        v => Err(CliError::InvalidPart(v.to_owned())),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, CliError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        v => Err(CliError::InvalidValue("--format", v.to_owned())),
+    }
+}
+
+fn parse_count(option: &'static str, value: &str) -> Result<usize, CliError> {
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(CliError::InvalidValue(option, value.to_owned())),
+    }
+}
+
+/// Parses a single value (79) or an exclusive range of values (79..93).
+fn parse_interval(option: &'static str, value: &str) -> Result<Interval, CliError> {
+    let number = |s: &str| {
+        s.parse::<i64>()
+            .map_err(|_| CliError::InvalidValue(option, value.to_owned()))
+    };
+
+    let interval = match value.split_once("..") {
+        Some((start, end)) => Interval::new(number(start)?, number(end)?),
+        None => Interval::from_start_len(number(value)?, 1),
+    };
+
+    if interval.is_empty() {
+        return Err(CliError::InvalidValue(option, value.to_owned()));
+    }
+    Ok(interval)
+}
+
+fn parse_days(arg: &str) -> Result<RangeInclusive<usize>, CliError> {
+    let day = |s: &str| {
+        s.parse::<usize>()
+            .map_err(|_| CliError::InvalidDay(arg.to_owned()))
+    };
+
+    let range = if let Some((first, last)) = arg.split_once("..=") {
+        day(first)?..=day(last)?
+    } else if let Some((first, end)) = arg.split_once("..") {
+        let end = day(end)?;
+        if end == 0 {
+            return Err(CliError::InvalidDay(arg.to_owned()));
+        }
+        day(first)?..=end - 1
+    } else {
+        let day = day(arg)?;
+        day..=day
+    };
+
+    if range.is_empty() {
+        return Err(CliError::InvalidDay(arg.to_owned()));
+    }
+
+    Ok(range)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    UnknownOption(String),
+    MissingValue(&'static str),
+    UnexpectedValue(String),
+    InvalidValue(&'static str, String),
+    CommandOnly(&'static str, &'static str),
+    InvalidPart(String),
+    InvalidDay(String),
+    AllWithDays,
+    Conflicting(&'static str, &'static str),
+    OnlyDay(&'static str, usize),
+    UnknownDay(usize),
+}
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownOption(o) => write!(f, "unknown option '{o}'"),
+            CliError::MissingValue(o) => write!(f, "option '{o}' requires a value"),
+            CliError::UnexpectedValue(o) => write!(f, "option '{o}' does not take a value"),
+            CliError::InvalidValue(o, v) => write!(f, "invalid value '{v}' for option '{o}'"),
+            CliError::CommandOnly(o, c) => write!(f, "option '{o}' is only valid for '{c}'"),
+            CliError::InvalidPart(p) => write!(f, "invalid part '{p}', expected 1 or 2"),
+            CliError::InvalidDay(d) => write!(f, "invalid day or day range '{d}'"),
+            CliError::AllWithDays => write!(f, "'--all' cannot be combined with a day selection"),
+            CliError::Conflicting(a, b) => write!(f, "'{a}' cannot be combined with '{b}'"),
+            CliError::OnlyDay(o, d) => write!(f, "'{o}' only works on day {d}"),
+            CliError::UnknownDay(d) => write!(f, "there is no solution for day {d}"),
+        }
+    }
+}
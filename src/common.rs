@@ -1,13 +1,48 @@
-use nom::{combinator::eof, sequence::terminated, IResult};
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod math;
+
+use nom::{
+    combinator::eof,
+    error::{Error, ErrorKind},
+    sequence::terminated,
+    IResult,
+};
+
+use crate::input::InputError;
 
 pub use nom::character::complete::i64;
 pub use nom::character::complete::u32;
 pub use nom::character::complete::u64;
 
-pub fn parse<'i, P, R>(parser: P, input: &'i str) -> R
+pub fn parse<'i, P, R>(parser: P, input: &'i str) -> Result<R, InputError>
 where
     P: FnMut(&'i str) -> IResult<&'i str, R>,
 {
-    let (_, r) = terminated(parser, eof)(input).unwrap();
-    r
+    match terminated(parser, eof)(input) {
+        Ok((_, r)) => Ok(r),
+        Err(nom::Err::Error(Error { input: rest, code }))
+        | Err(nom::Err::Failure(Error { input: rest, code })) => {
+            Err(InputError::at(input, rest, expected(code)))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(InputError::at(input, &input[input.len()..], "more input"))
+        }
+    }
+}
+
+/// Describes what a failing nom parser was looking for.
+fn expected(code: ErrorKind) -> String {
+    match code {
+        ErrorKind::Tag => "a literal tag".to_owned(),
+        ErrorKind::Digit => "a number".to_owned(),
+        ErrorKind::Char => "a specific character".to_owned(),
+        ErrorKind::CrLf => "a line ending".to_owned(),
+        ErrorKind::MultiSpace | ErrorKind::Space => "whitespace".to_owned(),
+        ErrorKind::Eof => "end of input".to_owned(),
+        ErrorKind::MapOpt | ErrorKind::MapRes => "a valid value".to_owned(),
+        ErrorKind::TakeUntil => "a terminator".to_owned(),
+        code => format!("a match for '{}'", code.description()),
+    }
 }
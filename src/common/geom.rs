@@ -0,0 +1,174 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position on a 2D plane, with `y` increasing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Adds a vector to this point, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Vector) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add(rhs.dx)?,
+            self.y.checked_add(rhs.dy)?,
+        ))
+    }
+
+    /// The distance between two points when only moving horizontally and
+    /// vertically.
+    pub fn taxicab_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance between two points when diagonal moves are also allowed.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.vector()
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub dx: i64,
+    pub dy: i64,
+}
+impl Vector {
+    pub const fn new(dx: i64, dy: i64) -> Self {
+        Self { dx, dy }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Vector::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector::new(self.dx * rhs, self.dy * rhs)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+/// A direction on the grid, listed clockwise starting from the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+impl Direction {
+    /// The four directions which don't move diagonally.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::DownRight => Vector::new(1, 1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// Turns 90 degrees anticlockwise.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Turns 90 degrees clockwise.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// Rotates clockwise by a number of 45 degree steps.
+    fn rotate(self, steps: usize) -> Direction {
+        Direction::ALL[(self as usize + steps) % Direction::ALL.len()]
+    }
+}
+
+/// Calculates a single step in the shoelace formula, which gives twice the
+/// signed area of a polygon when summed over all of its edges:
+/// https://en.wikipedia.org/wiki/Shoelace_formula
+pub fn shoelace(current: Point, next: Point) -> i64 {
+    (current.y + next.y) * (current.x - next.x)
+}
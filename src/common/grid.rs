@@ -0,0 +1,182 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    common::geom::{Direction, Point},
+    input::InputError,
+};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    /// Parses a grid from text, with one row per line. Every character is turned
+    /// into a cell by `cell`, which returns `None` for characters that are not
+    /// allowed in the grid.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Grid<T>, InputError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines() {
+            let mut row_width = 0;
+            for (offset, ch) in line.char_indices() {
+                let c = cell(ch)
+                    .ok_or_else(|| InputError::at(input, &line[offset..], "a valid grid cell"))?;
+                cells.push(c);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    let expected = format!("a row of {w} cells");
+                    return Err(InputError::at(input, line, expected));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(InputError::at(input, input, "at least one grid cell")),
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    /// Iterates over the cells in a column, from top to bottom. Panics if the
+    /// column is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} cells wide",
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Walks all points in the grid, from left to right, top to bottom.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height as i64).flat_map(move |y| (0..width as i64).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The points directly above, right of, below and left of a point, as far
+    /// as they lie within the grid.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ORTHOGONAL)
+    }
+
+    /// All points surrounding a point, including diagonals, as far as they lie
+    /// within the grid.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ALL)
+    }
+
+    fn neighbours(
+        &self,
+        point: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Point> + '_ {
+        directions.iter().filter_map(move |&d| {
+            let neighbour = point.checked_add(d.vector())?;
+            self.contains(neighbour).then_some(neighbour)
+        })
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} lies outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} lies outside of the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+
+/// A half-open range of integers, including `start` but excluding `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub const fn from_start_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start).max(0) as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values contained in both intervals. The result may be empty.
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Moves the interval by `offset`, keeping its length.
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.len() == 1 {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}..{}", self.start, self.end)
+        }
+    }
+}
+
+/// A set of integers, stored as sorted, non-overlapping intervals. Adjacent
+/// intervals are merged, so every set has exactly one representation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+impl IntervalSet {
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn contains(&self, value: i64) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let overlap = x.intersection(y);
+            if !overlap.is_empty() {
+                result.push(overlap);
+            }
+            // Drop whichever interval ends first, it can't overlap anything else.
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        IntervalSet { intervals: result }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        let mut others = other.intervals.iter().peekable();
+
+        for interval in self.iter() {
+            let mut start = interval.start;
+            // Skip over the intervals which lie entirely before this one.
+            while others.next_if(|o| o.end <= start).is_some() {}
+
+            while let Some(o) = others.peek().filter(|o| o.start < interval.end) {
+                if start < o.start {
+                    result.push(Interval::new(start, o.start));
+                }
+                start = start.max(o.end);
+                if o.end > interval.end {
+                    // This one may overlap the next interval too.
+                    break;
+                }
+                others.next();
+            }
+
+            if start < interval.end {
+                result.push(Interval::new(start, interval.end));
+            }
+        }
+
+        IntervalSet { intervals: result }
+    }
+
+    /// Cuts the intervals at every boundary that lies inside them, returning
+    /// the pieces in order.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn split_by(&self, boundaries: impl IntoIterator<Item = i64>) -> Vec<Interval> {
+        let mut boundaries: Vec<_> = boundaries.into_iter().collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut result = vec![];
+        for interval in self.iter() {
+            let first = boundaries.partition_point(|&b| b <= interval.start);
+            let mut start = interval.start;
+            for &boundary in boundaries[first..]
+                .iter()
+                .take_while(|&&b| b < interval.end)
+            {
+                result.push(Interval::new(start, boundary));
+                start = boundary;
+            }
+            result.push(Interval::new(start, interval.end));
+        }
+        result
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "(empty)");
+        }
+        for (idx, interval) in self.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{interval}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        sorted.sort_unstable();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+/// A function which adds a fixed offset to all values within an interval, for
+/// a number of non-overlapping intervals. Values outside of those intervals are
+/// mapped to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    pieces: Vec<(Interval, i64)>,
+}
+impl OffsetMap {
+    /// Creates a map from intervals with their offsets. Returns `None` if any of
+    /// the intervals overlap.
+    pub fn new(pieces: impl IntoIterator<Item = (Interval, i64)>) -> Option<OffsetMap> {
+        let mut pieces: Vec<_> = pieces
+            .into_iter()
+            .filter(|(interval, _)| !interval.is_empty())
+            .collect();
+        pieces.sort_unstable_by_key(|(interval, _)| interval.start);
+
+        if pieces.windows(2).any(|w| w[0].0.end > w[1].0.start) {
+            return None;
+        }
+        Some(OffsetMap { pieces })
+    }
+
+    /// The offset applied to a value.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn offset(&self, value: i64) -> i64 {
+        let idx = self.pieces.partition_point(|(i, _)| i.end <= value);
+        match self.pieces.get(idx) {
+            Some((interval, offset)) if interval.contains(value) => *offset,
+            _ => 0,
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn get(&self, value: i64) -> i64 {
+        value + self.offset(value)
+    }
+
+    /// Maps every value in the set.
+    pub fn apply(&self, set: &IntervalSet) -> IntervalSet {
+        set.iter()
+            .flat_map(|interval| self.split(interval))
+            .map(|(interval, offset)| interval.shift(offset))
+            .collect()
+    }
+
+    /// Finds all values which are mapped into the set.
+    pub fn preimage(&self, set: &IntervalSet) -> IntervalSet {
+        self.split(Self::DOMAIN)
+            .flat_map(|(source, offset)| {
+                set.intersection(&source.shift(offset).into())
+                    .iter()
+                    .map(move |target| target.shift(-offset))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Composes two maps into one, which gives the same result as applying this
+    /// map first, and `next` after it.
+    pub fn then(&self, next: &OffsetMap) -> OffsetMap {
+        let mut pieces: Vec<(Interval, i64)> = vec![];
+
+        for (source, offset) in self.split(Self::DOMAIN) {
+            for (target, next_offset) in next.split(source.shift(offset)) {
+                let piece = (target.shift(-offset), offset + next_offset);
+                match pieces.last_mut() {
+                    Some(last) if last.0.end == piece.0.start && last.1 == piece.1 => {
+                        last.0.end = piece.0.end
+                    }
+                    _ => pieces.push(piece),
+                }
+            }
+        }
+
+        pieces.retain(|&(_, offset)| offset != 0);
+        OffsetMap { pieces }
+    }
+
+    /// All values the map can be applied to. `i64::MAX` is left out, so the
+    /// domain is still a half-open interval.
+    const DOMAIN: Interval = Interval::new(i64::MIN, i64::MAX);
+
+    /// Cuts an interval into the parts that fall within the same piece of the
+    /// map, or in the same gap between pieces, along with their offsets.
+    fn split(&self, interval: Interval) -> impl Iterator<Item = (Interval, i64)> + '_ {
+        let first = self
+            .pieces
+            .partition_point(|(i, _)| i.end <= interval.start);
+        let mut pieces = self.pieces[first..].iter().peekable();
+        let mut start = interval.start;
+
+        std::iter::from_fn(move || {
+            if start >= interval.end {
+                return None;
+            }
+            let part = match pieces.peek() {
+                // Inside a piece, so the values move by its offset.
+                Some(&&(piece, offset)) if piece.start <= start => {
+                    pieces.next();
+                    (Interval::new(start, piece.end.min(interval.end)), offset)
+                }
+                // In a gap before the next piece, so the values stay put.
+                next => {
+                    let end = next.map_or(interval.end, |(p, _)| p.start.min(interval.end));
+                    (Interval::new(start, end), 0)
+                }
+            };
+            start = part.0.end;
+            Some(part)
+        })
+    }
+}
@@ -0,0 +1,99 @@
+/// Greatest common divisor, using Euclid's algorithm.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple. Returns 0 if either number is 0.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// Greatest common divisor of all numbers, or 0 if there are none.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all numbers, or 1 if there are none.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+/// Finds `(g, x, y)` such that `a * x + b * y = g`, where `g` is the greatest
+/// common divisor of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Finds `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if it exists.
+/// The modulus must be positive.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    debug_assert!(modulus > 0, "modulus must be positive, got {modulus}");
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` using the
+/// Chinese Remainder Theorem. The moduli must be positive, but do not need
+/// to be coprime.
+///
+/// Returns the smallest non-negative solution together with the least common
+/// multiple of the moduli, so that every solution is `x + k * lcm`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Result<(i64, i64), CrtError> {
+    let (mut x, mut m) = (0i128, 1i128);
+    let narrow = |n: i128| i64::try_from(n).map_err(|_| CrtError::Overflow);
+
+    for (residue, modulus) in congruences {
+        debug_assert!(modulus > 0, "modulus must be positive, got {modulus}");
+        let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+        let (g, p, _) = extended_gcd(narrow(m)?, narrow(modulus)?);
+        let g = i128::from(g);
+
+        let diff = residue - x;
+        if diff % g != 0 {
+            return Err(CrtError::Contradiction);
+        }
+
+        // Step from x in multiples of m until the new congruence holds.
+        let step = modulus / g;
+        let k = (diff / g * i128::from(p)).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+
+    Ok((narrow(x)?, narrow(m)?))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// No number satisfies all of the congruences.
+    Contradiction,
+    /// The least common multiple of the moduli does not fit in an `i64`.
+    Overflow,
+}
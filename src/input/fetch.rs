@@ -0,0 +1,163 @@
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable holding the URL inputs are downloaded from. Inputs are
+/// requested from `<base URL>/day/<N>/input`.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable holding the session cookie to authenticate with.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub base_url: String,
+    pub session: String,
+}
+impl FetchConfig {
+    pub fn from_env() -> Result<FetchConfig, FetchError> {
+        let var = |name| std::env::var(name).map_err(|_| FetchError::MissingVar(name));
+
+        Ok(FetchConfig {
+            base_url: var(BASE_URL_VAR)?,
+            session: var(SESSION_VAR)?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already present, so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input for a day is present in the input directory, downloading
+/// it if it isn't there yet. Inputs never change, so existing files are kept.
+pub fn fetch_day(
+    input_dir: &Path,
+    day_no: usize,
+    config: &FetchConfig,
+) -> Result<Fetched, FetchError> {
+    let path = input_dir.join(format!("day{day_no:02}.txt"));
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = download(config, &format!("/day/{day_no}/input"))?;
+
+    // Write to a temporary file first, so an interrupted download doesn't leave
+    // behind a partial input that would be mistaken for a cached one.
+    let partial = path.with_extension("txt.partial");
+    fs::create_dir_all(input_dir)
+        .and_then(|_| fs::write(&partial, input))
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|e| FetchError::Io(path.clone(), e))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Performs a plain HTTP/1.0 GET request, returning the response body.
+fn download(config: &FetchConfig, path: &str) -> Result<Vec<u8>, FetchError> {
+    let url = || FetchError::InvalidUrl(config.base_url.clone());
+    let rest = config.base_url.strip_prefix("http://").ok_or_else(url)?;
+    let (authority, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    if authority.is_empty() {
+        return Err(url());
+    }
+    let address = if authority.contains(':') {
+        authority.to_owned()
+    } else {
+        format!("{authority}:80")
+    };
+    let target = match prefix.trim_matches('/') {
+        "" => path.to_owned(),
+        prefix => format!("/{prefix}{path}"),
+    };
+
+    let io_error = |e| FetchError::Request(config.base_url.clone(), e);
+    let mut stream = TcpStream::connect(&address).map_err(io_error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(io_error)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(io_error)?;
+
+    write!(
+        stream,
+        "GET {target} HTTP/1.0\r\nHost: {authority}\r\nCookie: session={}\r\nUser-Agent: {}\r\n\r\n",
+        config.session,
+        concat!("github.com/geluk/advent-of-code-2023 v", env!("CARGO_PKG_VERSION")),
+    )
+    .map_err(io_error)?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response).map_err(io_error)?;
+    parse_response(&response)
+}
+
+fn parse_response(response: &[u8]) -> Result<Vec<u8>, FetchError> {
+    let header_end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or(FetchError::InvalidResponse)?;
+    let head =
+        std::str::from_utf8(&response[..header_end]).map_err(|_| FetchError::InvalidResponse)?;
+    let body = &response[header_end + 4..];
+
+    let status_line = head.lines().next().unwrap_or_default();
+    let mut parts = status_line.splitn(3, ' ');
+    let (Some(version), Some(code)) = (parts.next(), parts.next()) else {
+        return Err(FetchError::InvalidResponse);
+    };
+    let code: u16 = code.parse().map_err(|_| FetchError::InvalidResponse)?;
+    if !version.starts_with("HTTP/") {
+        return Err(FetchError::InvalidResponse);
+    }
+    if code != 200 {
+        let reason = parts.next().unwrap_or_default().to_owned();
+        return Err(FetchError::Status(code, reason));
+    }
+
+    Ok(body.to_vec())
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingVar(&'static str),
+    InvalidUrl(String),
+    Request(String, io::Error),
+    InvalidResponse,
+    Status(u16, String),
+    Io(PathBuf, io::Error),
+    /// Inputs are embedded in the binary, so there is nowhere to store them.
+    #[cfg(feature = "embedded-inputs")]
+    Embedded,
+}
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingVar(var) => write!(f, "environment variable {var} is not set"),
+            FetchError::InvalidUrl(url) => {
+                write!(
+                    f,
+                    "invalid base URL '{url}', expected http://<host>[:port][/path]"
+                )
+            }
+            FetchError::Request(url, e) => write!(f, "request to '{url}' failed: {e}"),
+            FetchError::InvalidResponse => write!(f, "the server sent an invalid response"),
+            FetchError::Status(code, reason) => {
+                write!(f, "the server responded with {code} {reason}")
+            }
+            FetchError::Io(path, e) => write!(f, "could not write input '{}': {e}", path.display()),
+            #[cfg(feature = "embedded-inputs")]
+            FetchError::Embedded => write!(
+                f,
+                "inputs are embedded, use --input-dir or {} to pick a directory to fetch into",
+                super::INPUT_DIR_VAR
+            ),
+        }
+    }
+}
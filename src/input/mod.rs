@@ -1,3 +1,26 @@
+mod fetch;
+
+pub use fetch::{FetchConfig, FetchError, Fetched};
+
+use crate::answer::{AnswerSheet, AnswerSheetError};
+
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Environment variable that may be used to point to a different input directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[cfg(not(feature = "embedded-inputs"))]
+const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input");
+
+#[cfg(feature = "embedded-inputs")]
+const ANSWERS: &str = include_str!("answers.toml");
+
+#[cfg(feature = "embedded-inputs")]
 const DAYS: [&str; 11] = [
     include_str!("day01.txt"),
     include_str!("day02.txt"),
@@ -12,26 +35,212 @@ const DAYS: [&str; 11] = [
     include_str!("day11.txt"),
 ];
 
-pub fn load_day<I: DayInput>(day_no: usize) -> I {
-    let day = DAYS[day_no - 1];
+/// Inputs that have already been read from disk, keyed by their path.
+/// Parsed inputs may borrow from their source text, so once read, an input is
+/// kept around for the remainder of the program.
+static LOADED: Mutex<BTreeMap<PathBuf, &'static str>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    Directory(PathBuf),
+    #[cfg(feature = "embedded-inputs")]
+    Embedded,
+}
+impl InputSource {
+    /// Determines where to load inputs from. An explicitly passed directory takes
+    /// precedence over the directory set through [`INPUT_DIR_VAR`].
+    pub fn new(input_dir: Option<PathBuf>) -> Self {
+        match input_dir.or_else(|| std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from)) {
+            Some(dir) => InputSource::Directory(dir),
+            #[cfg(feature = "embedded-inputs")]
+            None => InputSource::Embedded,
+            #[cfg(not(feature = "embedded-inputs"))]
+            None => InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR)),
+        }
+    }
+
+    /// Reads the known answers for the inputs. If there is no answers file, the
+    /// answer sheet is empty.
+    pub fn read_answers(&self) -> Result<AnswerSheet, LoadError> {
+        let text = match self {
+            InputSource::Directory(dir) => {
+                let path = dir.join("answers.toml");
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => text,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+                    Err(source) => return Err(LoadError::Io { path, source }),
+                }
+            }
+            #[cfg(feature = "embedded-inputs")]
+            InputSource::Embedded => ANSWERS.to_owned(),
+        };
+
+        AnswerSheet::parse(&text).map_err(LoadError::Answers)
+    }
+
+    /// Whether there is an input for a day, without reading it.
+    pub fn has_day(&self, day_no: usize) -> bool {
+        match self {
+            InputSource::Directory(dir) => dir.join(format!("day{day_no:02}.txt")).is_file(),
+            #[cfg(feature = "embedded-inputs")]
+            InputSource::Embedded => (1..=DAYS.len()).contains(&day_no),
+        }
+    }
+
+    pub fn read_day(&self, day_no: usize) -> Result<&'static str, LoadError> {
+        match self {
+            InputSource::Directory(dir) => read_file(&dir.join(format!("day{day_no:02}.txt"))),
+            #[cfg(feature = "embedded-inputs")]
+            InputSource::Embedded => DAYS
+                .get(day_no - 1)
+                .copied()
+                .ok_or(LoadError::NotEmbedded(day_no)),
+        }
+    }
+}
+
+impl InputSource {
+    /// Downloads the input for a day into the input directory, unless it is
+    /// already there.
+    pub fn fetch_day(&self, day_no: usize, config: &FetchConfig) -> Result<Fetched, FetchError> {
+        match self {
+            InputSource::Directory(dir) => fetch::fetch_day(dir, day_no, config),
+            #[cfg(feature = "embedded-inputs")]
+            InputSource::Embedded => Err(FetchError::Embedded),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<&'static str, LoadError> {
+    let mut loaded = LOADED.lock().unwrap();
+    if let Some(input) = loaded.get(path) {
+        return Ok(input);
+    }
+
+    let input = std::fs::read_to_string(path)
+        .map_err(|source| LoadError::Io {
+            path: path.to_owned(),
+            source,
+        })?
+        .leak();
+    loaded.insert(path.to_owned(), input);
+    Ok(input)
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse(InputError),
+    Answers(AnswerSheetError),
+    #[cfg(feature = "embedded-inputs")]
+    NotEmbedded(usize),
+}
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => {
+                write!(f, "could not read input '{}': {source}", path.display())
+            }
+            LoadError::Parse(e) => write!(f, "could not parse input: {e}"),
+            LoadError::Answers(e) => write!(f, "could not parse answers file: {e}"),
+            #[cfg(feature = "embedded-inputs")]
+            LoadError::NotEmbedded(day_no) => write!(f, "no input embedded for day {day_no}"),
+        }
+    }
+}
+
+impl From<InputError> for LoadError {
+    fn from(e: InputError) -> Self {
+        LoadError::Parse(e)
+    }
+}
 
-    I::load(day)
+/// Describes where and why an input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub day: Option<usize>,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number in characters, starting at 1.
+    pub column: usize,
+    /// The line of input on which the error occurred.
+    pub snippet: String,
+    pub expected: String,
+}
+impl InputError {
+    /// Creates an error for the position in `input` at which `remaining` starts.
+    /// `remaining` must be a subslice of `input`.
+    pub fn at(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let (before, after) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = after.find('\n').map_or(input.len(), |i| offset + i);
+
+        Self {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn for_day(self, day_no: usize) -> Self {
+        Self {
+            day: Some(day_no),
+            ..self
+        }
+    }
+
+    /// Moves the error down by a number of lines, for errors produced by parsers
+    /// that only saw part of the input.
+    pub fn offset_lines(self, lines: usize) -> Self {
+        Self {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:>col$}", "", "^", col = self.column)
+    }
 }
 
-pub trait DayInput {
-    fn load(input: &'static str) -> Self;
+pub trait DayInput: Sized {
+    fn load(input: &'static str) -> Result<Self, InputError>;
 }
 
 impl DayInput for &str {
-    fn load(input: &'static str) -> Self {
-        input
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        Ok(input)
     }
 }
 
 impl<T: DayInput> DayInput for Vec<T> {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         let mut lines_vec = Vec::with_capacity(1000);
-        lines_vec.extend(input.lines().map(T::load));
-        lines_vec
+        for (idx, line) in input.lines().enumerate() {
+            lines_vec.push(T::load(line).map_err(|e| e.offset_lines(idx))?);
+        }
+        Ok(lines_vec)
     }
 }
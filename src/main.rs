@@ -1,77 +1,426 @@
+mod answer;
+mod bench;
+mod check;
+mod cli;
 mod common;
 mod input;
+mod parallel;
+mod registry;
+mod report;
 mod solutions;
 
-use std::time::{Duration, Instant};
+use std::{
+    io::{self, Write},
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant},
+};
 
-use input::DayInput;
-use solutions::*;
+use answer::{Answer, SolveError};
+use bench::Baseline;
+use cli::{BenchOptions, CliError, Command, Options, Part, Selection};
+use common::interval::Interval;
+use input::{DayInput, FetchConfig, Fetched, InputSource, LoadError};
+use registry::DynDay;
+use report::Format;
+use solutions::DAYS;
 
 trait Day {
-    type Input: DayInput;
+    type Input: DayInput + Sync;
     const DAY_NO: usize;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64;
-    fn solve_challenge_2(input: &Self::Input) -> u64;
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError>;
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError>;
 }
 
-fn main() {
+fn main() -> ExitCode {
+    let options = match Options::from_env() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if options.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let days = match select_days(&options.selection) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!(
+                "error: {e} (available days: {}..={})",
+                DAYS.first_day(),
+                DAYS.last_day()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let inputs = InputSource::new(options.input_dir.clone());
+    // When no days are selected, only run the days there is an input for. Fetching
+    // is all about the missing inputs though, so it still gets every day.
+    let days = match (&options.selection, &options.command) {
+        (Selection::All, Command::Solve | Command::Bench(_)) => days
+            .into_iter()
+            .filter(|day| inputs.has_day(day.day_no()))
+            .collect(),
+        _ => days,
+    };
+
+    match &options.command {
+        Command::Solve => solve(&days, &inputs, &options),
+        Command::Bench(bench_options) => bench(&days, &inputs, bench_options),
+        Command::Fetch => fetch(&days, &inputs),
+        Command::Trace(seeds) => trace(&inputs, *seeds),
+        Command::SeedsFor(locations) => seeds_for(&inputs, *locations),
+        Command::Graph { cycles } => graph(&inputs, *cycles),
+        Command::Visualize => visualize(&inputs),
+    }
+}
+
+/// Looks up the selected days, in the order they were given. Days selected
+/// more than once are only included the first time.
+fn select_days(selection: &Selection) -> Result<Vec<&'static dyn DynDay>, CliError> {
+    let ranges = match selection {
+        Selection::All => return Ok(DAYS.iter().collect()),
+        Selection::Days(ranges) => ranges,
+    };
+
+    let mut days: Vec<&dyn DynDay> = vec![];
+    // Ranges can be far larger than the registry, but stop at the first unknown day.
+    for day_no in ranges.iter().cloned().flatten() {
+        let day = DAYS.get(day_no).ok_or(CliError::UnknownDay(day_no))?;
+        if days.iter().all(|d| d.day_no() != day_no) {
+            days.push(day);
+        }
+    }
+    Ok(days)
+}
+
+fn solve(days: &[&dyn DynDay], inputs: &InputSource, options: &Options) -> ExitCode {
+    let answers = match options.check.then(|| inputs.read_answers()).transpose() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = SolveConfig {
+        part: options.part,
+        parallel_parts: options.parallel_parts,
+    };
     let now = Instant::now();
+    let results = if options.parallel {
+        parallel::solve_days(days, inputs, config)
+    } else {
+        days.iter().map(|d| d.solve(inputs, config)).collect()
+    };
+    let elapsed = now.elapsed();
 
-    solve_day::<Day01>();
-    solve_day::<Day02>();
-    solve_day::<Day03>();
-    solve_day::<Day04>();
-    solve_day::<Day05>();
-    solve_day::<Day06>();
-    solve_day::<Day07>();
-    solve_day::<Day08>();
-    solve_day::<Day09>();
-    solve_day::<Day10>();
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut solutions = vec![];
 
-    let elapsed = now.elapsed().as_micros() as f32 / 1000.0;
+    for result in results {
+        match result {
+            Ok(solution) => {
+                if solution.parts().any(|(_, p)| p.answer.is_err()) {
+                    exit_code = ExitCode::FAILURE;
+                }
+                if let Some(answers) = &answers {
+                    let passed = if options.format == Format::Text {
+                        check::print_check(answers, &solution)
+                    } else {
+                        check::all_passed(answers, &solution)
+                    };
+                    if !passed {
+                        exit_code = ExitCode::FAILURE;
+                    }
+                } else if options.format == Format::Text {
+                    print_solution(&solution);
+                }
+                solutions.push(solution);
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
 
-    println!("🔥blazing fast🔥: all solutions calculated in {elapsed} ms",)
+    let records = || -> Vec<_> {
+        solutions
+            .iter()
+            .flat_map(|s| report::records(s, answers.as_ref()))
+            .collect()
+    };
+    let written = match options.format {
+        Format::Text => {
+            let ms = |d: Duration| d.as_micros() as f32 / 1000.0;
+            println!(
+                "🔥blazing fast🔥: all solutions calculated in {} ms",
+                ms(elapsed)
+            );
+            if options.parallel {
+                let total = solutions.iter().map(Solution::total_time).sum();
+                println!(
+                    "   (adding up to {} ms when solved one after another)",
+                    ms(total)
+                );
+            }
+            Ok(())
+        }
+        Format::Json => report::write_json(io::stdout().lock(), &records()),
+        Format::Csv => report::write_csv(io::stdout().lock(), &records()),
+    };
+
+    if let Err(e) = written {
+        eprintln!("error: could not write output: {e}");
+        exit_code = ExitCode::FAILURE;
+    }
+    exit_code
 }
 
-fn solve_day<D: Day>() {
-    let s = calculate_solution::<D>();
-    println!("Day {}:", D::DAY_NO);
+fn bench(days: &[&dyn DynDay], inputs: &InputSource, options: &BenchOptions) -> ExitCode {
+    // Load the baseline first, so we don't find out it's missing after benchmarking.
+    let baseline = match options.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("error: could not read baseline: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut results = vec![];
+
+    bench::print_header();
+    for day in days {
+        match day.bench(inputs, options.config) {
+            Ok(result) => {
+                bench::print_day(&result);
+                results.push(result);
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        let comparisons = baseline.compare(&results, options.threshold);
+        bench::print_comparison(&comparisons, options.threshold);
+        if comparisons.iter().any(|c| c.regressed) {
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        if let Err(e) = Baseline::from_results(&results).save(path) {
+            eprintln!("error: could not save baseline: {e}");
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+fn fetch(days: &[&dyn DynDay], inputs: &InputSource) -> ExitCode {
+    let config = match FetchConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for day in days {
+        match inputs.fetch_day(day.day_no(), &config) {
+            Ok(Fetched::Downloaded(path)) => println!("downloaded {}", path.display()),
+            Ok(Fetched::Cached(path)) => println!("already present: {}", path.display()),
+            Err(e) => {
+                eprintln!("error: could not fetch day {}: {e}", day.day_no());
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
+
+/// Reads and parses the input of a single day.
+fn load_day<D: Day>(inputs: &InputSource) -> Result<D::Input, LoadError> {
+    let raw = inputs.read_day(D::DAY_NO)?;
+    D::Input::load(raw).map_err(|e| e.for_day(D::DAY_NO).into())
+}
+
+/// Prints the values a range of seeds corresponds to at every stage of day 5.
+fn trace(inputs: &InputSource, seeds: Interval) -> ExitCode {
+    let almanac = match load_day::<solutions::Day05>(inputs) {
+        Ok(almanac) => almanac,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for stage in almanac.trace(seeds) {
+        println!("{:<12} {}", stage.category, stage.values);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Prints the seeds of day 5 that are planted in a range of locations.
+fn seeds_for(inputs: &InputSource, locations: Interval) -> ExitCode {
+    let almanac = match load_day::<solutions::Day05>(inputs) {
+        Ok(almanac) => almanac,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{}", almanac.seeds_landing_in(&locations.into()));
+    ExitCode::SUCCESS
+}
+
+/// Prints the day 8 network in Graphviz DOT format.
+fn graph(inputs: &InputSource, cycles: bool) -> ExitCode {
+    let (instructions, map) = match load_day::<solutions::Day08>(inputs) {
+        Ok(network) => network,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let instructions = cycles.then_some(&instructions[..]);
+    if let Err(e) = map.write_dot(io::stdout().lock(), instructions) {
+        eprintln!("error: could not write the graph: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Draws the pipes of day 10.
+fn visualize(inputs: &InputSource) -> ExitCode {
+    let map = match load_day::<solutions::Day10>(inputs) {
+        Ok(map) => map,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(visualization) = map.visualize() else {
+        eprintln!("error: there is no loop through the start tile");
+        return ExitCode::FAILURE;
+    };
+    if let Err(e) = writeln!(io::stdout().lock(), "{visualization}") {
+        eprintln!("error: could not write the map: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn print_solution(s: &Solution) {
+    println!("Day {}:", s.day_no);
     println!(" - parsed input (in {}µs)", s.t_input.as_micros());
-    println!(
-        " - first answer: {} (in {}µs)",
-        s.answer_1,
-        s.t_1.as_micros()
-    );
-    println!(
-        " - second answer: {} (in {}µs)",
-        s.answer_2,
-        s.t_2.as_micros()
-    );
-}
-
-struct Solution {
-    answer_1: u64,
-    answer_2: u64,
+    if let Some(p) = &s.part_1 {
+        print_part("first", p);
+    }
+    if let Some(p) = &s.part_2 {
+        print_part("second", p);
+    }
+}
+
+fn print_part(name: &str, p: &PartSolution) {
+    let time = p.time.as_micros();
+    match &p.answer {
+        Ok(answer) if answer.is_multiline() => {
+            println!(" - {name} answer (in {time}µs):");
+            for line in answer.to_string().lines() {
+                println!("     {line}");
+            }
+        }
+        Ok(answer) => println!(" - {name} answer: {answer} (in {time}µs)"),
+        Err(e) => println!(" - {name} answer: failed, {e} (in {time}µs)"),
+    }
+}
+
+pub struct Solution {
+    day_no: usize,
     t_input: Duration,
-    t_1: Duration,
-    t_2: Duration,
+    part_1: Option<PartSolution>,
+    part_2: Option<PartSolution>,
 }
 
-fn calculate_solution<D: Day>() -> Solution {
-    let (input, t_input) = measure(|| input::load_day(D::DAY_NO));
-    let (answer_1, t_1) = measure(|| D::solve_challenge_1(&input));
-    let (answer_2, t_2) = measure(|| D::solve_challenge_2(&input));
+impl Solution {
+    /// Time spent parsing and solving, regardless of whether it happened in parallel.
+    fn total_time(&self) -> Duration {
+        self.t_input + self.parts().map(|(_, p)| p.time).sum::<Duration>()
+    }
 
-    Solution {
-        answer_1,
-        answer_2,
-        t_input,
-        t_1,
-        t_2,
+    fn parts(&self) -> impl Iterator<Item = (Part, &PartSolution)> {
+        [(Part::One, &self.part_1), (Part::Two, &self.part_2)]
+            .into_iter()
+            .filter_map(|(part, p)| Some((part, p.as_ref()?)))
     }
 }
 
+pub struct PartSolution {
+    answer: Result<Answer, SolveError>,
+    time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SolveConfig {
+    /// Solves only this part, or both parts if unset.
+    pub part: Option<Part>,
+    /// Solves both parts on separate threads.
+    pub parallel_parts: bool,
+}
+
+fn calculate_solution<D: Day>(
+    inputs: &InputSource,
+    config: SolveConfig,
+) -> Result<Solution, LoadError> {
+    let raw_input = inputs.read_day(D::DAY_NO)?;
+    let (input, t_input) = measure(|| D::Input::load(raw_input));
+    let input = input.map_err(|e| e.for_day(D::DAY_NO))?;
+    let solve_part = |p: Part, solver: fn(&D::Input) -> Result<Answer, SolveError>| {
+        if config.part.is_some_and(|selected| selected != p) {
+            return None;
+        }
+        let (answer, time) = measure(|| solver(&input));
+        Some(PartSolution { answer, time })
+    };
+
+    let (part_1, part_2) = if config.parallel_parts {
+        thread::scope(|scope| {
+            let part_2 = scope.spawn(|| solve_part(Part::Two, D::solve_challenge_2));
+            let part_1 = solve_part(Part::One, D::solve_challenge_1);
+            (part_1, part_2.join().unwrap())
+        })
+    } else {
+        (
+            solve_part(Part::One, D::solve_challenge_1),
+            solve_part(Part::Two, D::solve_challenge_2),
+        )
+    };
+
+    Ok(Solution {
+        day_no: D::DAY_NO,
+        t_input,
+        part_1,
+        part_2,
+    })
+}
+
 fn measure<F, R>(action: F) -> (R, Duration)
 where
     F: FnOnce() -> R,
@@ -85,57 +434,963 @@ where
 mod tests {
 
     use super::*;
+    use answer::AnswerSheet;
+    use itertools::Itertools;
+    use solutions::*;
+
+    fn parse_args(args: &str) -> Result<Options, CliError> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_day_selection() {
+        let options = parse_args("--part 2 3..=5 7 9..11").unwrap();
+
+        assert_eq!(Command::Solve, options.command);
+        assert_eq!(
+            Selection::Days(vec![3..=5, 7..=7, 9..=10]),
+            options.selection
+        );
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(Selection::All, parse_args("--all").unwrap().selection);
+        assert_eq!(Selection::All, parse_args("").unwrap().selection);
+        assert_eq!(Command::Fetch, parse_args("fetch 3").unwrap().command);
+        assert_eq!(
+            Command::Graph { cycles: true },
+            parse_args("graph --cycles").unwrap().command
+        );
+        assert_eq!(
+            Command::Visualize,
+            parse_args("--visualize 10").unwrap().command
+        );
+        assert_eq!(
+            Command::Trace(Interval::new(79, 93)),
+            parse_args("--trace=79..93").unwrap().command
+        );
+        assert_eq!(
+            Command::SeedsFor(Interval::new(35, 36)),
+            parse_args("--seeds-for 35").unwrap().command
+        );
+    }
+
+    #[test]
+    fn select_days_from_ranges() {
+        let day_numbers = |args| {
+            let options = parse_args(args).unwrap();
+            select_days(&options.selection).map(|days| days.iter().map(|d| d.day_no()).collect())
+        };
+
+        assert_eq!(Ok(vec![5, 3, 4]), day_numbers("5 3..=5 4 5"));
+        assert_eq!(Err(CliError::UnknownDay(13)), day_numbers("1..=9999999999"));
+        assert_eq!(
+            Err(CliError::UnknownDay(9999999999)),
+            day_numbers("9999999999")
+        );
+    }
+
+    #[test]
+    fn parse_invalid_arguments() {
+        assert!(matches!(parse_args("5..3"), Err(CliError::InvalidDay(_))));
+        assert!(matches!(
+            parse_args("--part 3"),
+            Err(CliError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            parse_args("--part"),
+            Err(CliError::MissingValue(_))
+        ));
+        assert!(matches!(parse_args("--all 4"), Err(CliError::AllWithDays)));
+        assert!(matches!(
+            parse_args("--trace 9..9"),
+            Err(CliError::InvalidValue("--trace", _))
+        ));
+        assert!(matches!(
+            parse_args("fetch --samples 3"),
+            Err(CliError::CommandOnly("--samples", "bench"))
+        ));
+        assert!(matches!(
+            parse_args("--cycles"),
+            Err(CliError::CommandOnly("--cycles", "graph"))
+        ));
+        assert!(matches!(
+            parse_args("bench --trace 79"),
+            Err(CliError::Conflicting("--trace", "bench"))
+        ));
+        assert!(matches!(
+            parse_args("--visualize --trace 79"),
+            Err(CliError::Conflicting("--trace", "--visualize"))
+        ));
+        assert!(matches!(
+            parse_args("--trace 79 3"),
+            Err(CliError::OnlyDay("--trace", 5))
+        ));
+        assert!(matches!(
+            parse_args("--trace 79 --part 1"),
+            Err(CliError::Conflicting("--part", "--trace"))
+        ));
+        assert!(matches!(
+            parse_args("--seeds-for 35 --format=json"),
+            Err(CliError::Conflicting("--format", "--seeds-for"))
+        ));
+        assert!(matches!(
+            parse_args("bench --part 1 --format json 6"),
+            Err(CliError::Conflicting("--part", "bench"))
+        ));
+        assert!(matches!(
+            parse_args("fetch --check"),
+            Err(CliError::Conflicting("--check", "fetch"))
+        ));
+        assert!(matches!(
+            parse_args("graph 5"),
+            Err(CliError::OnlyDay("graph", 8))
+        ));
+        assert!(matches!(
+            parse_args("--visualize 3"),
+            Err(CliError::OnlyDay("--visualize", 10))
+        ));
+        assert!(matches!(
+            parse_args("--visualize --all"),
+            Err(CliError::OnlyDay("--visualize", 10))
+        ));
+        assert!(matches!(
+            parse_args("--fast"),
+            Err(CliError::UnknownOption(_))
+        ));
+    }
+
+    #[test]
+    fn report_input_error_location() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 red, 2 grey\n";
+        let error = <Day02 as Day>::Input::load(input).err().unwrap();
+
+        assert_eq!(2, error.line);
+        assert_eq!(18, error.column);
+        assert_eq!("a literal tag", error.expected);
+        assert_eq!("Game 2: 1 red, 2 grey", error.snippet);
+    }
+
+    #[test]
+    fn stored_answers() {
+        let inputs = InputSource::new(None);
+        let answers = inputs.read_answers().unwrap();
+        let mut failures = vec![];
+        let mut checked = 0;
+
+        for day in DAYS.iter() {
+            let solution = match day.solve(&inputs, SolveConfig::default()) {
+                Ok(solution) => solution,
+                Err(LoadError::Io { .. }) => continue,
+                #[cfg(feature = "embedded-inputs")]
+                Err(LoadError::NotEmbedded(_)) => continue,
+                Err(e) => panic!("{e}"),
+            };
+
+            for check in check::check_solution(&answers, &solution) {
+                match check.verdict {
+                    check::Verdict::Pass => checked += 1,
+                    check::Verdict::Fail { expected, got } => failures.push(format!(
+                        "day {} {:?}: expected {expected}, got {got}",
+                        day.day_no(),
+                        check.part
+                    )),
+                    check::Verdict::Error(e) => {
+                        failures.push(format!("day {} {:?}: {e}", day.day_no(), check.part))
+                    }
+                    check::Verdict::Unknown { .. } => {}
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "wrong answers:\n{}",
+            failures.join("\n")
+        );
+        let known = DAYS
+            .iter()
+            .flat_map(|d| [(d, Part::One), (d, Part::Two)])
+            .filter(|(d, part)| answers.get(d.day_no(), *part).is_some())
+            .count();
+        assert_eq!(known, checked);
+    }
+
+    #[test]
+    fn parallel_results_keep_day_order() {
+        let inputs = InputSource::new(None);
+        let mut days: Vec<_> = DAYS.iter().collect();
+        days.reverse();
+        let config = SolveConfig {
+            part: None,
+            parallel_parts: true,
+        };
+
+        let results = parallel::solve_days(&days, &inputs, config);
+
+        assert_eq!(days.len(), results.len());
+        for (day, result) in days.iter().zip(results) {
+            match result {
+                Ok(solution) => {
+                    assert_eq!(day.day_no(), solution.day_no);
+                    assert_eq!(2, solution.parts().count());
+                }
+                Err(LoadError::Io { path, .. }) => {
+                    assert!(path.ends_with(format!("day{:02}.txt", day.day_no())))
+                }
+                #[cfg(feature = "embedded-inputs")]
+                Err(LoadError::NotEmbedded(day_no)) => assert_eq!(day.day_no(), day_no),
+                Err(e) => panic!("{e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_answer_sheet() {
+        let sheet = AnswerSheet::parse(
+            "# comment\n[day03]\npart1 = 12\npart2 = -4\n\n[day10]\npart1 = \"ABC\"\npart2 = [\"#.\", \".#\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(Some(&Answer::Unsigned(12)), sheet.get(3, Part::One));
+        assert_eq!(Some(&Answer::Signed(-4)), sheet.get(3, Part::Two));
+        assert_eq!(Some(&Answer::from("ABC")), sheet.get(10, Part::One));
+        assert_eq!(
+            Some(&Answer::from(vec!["#.".to_owned(), ".#".to_owned()])),
+            sheet.get(10, Part::Two)
+        );
+        assert_eq!(None, sheet.get(4, Part::One));
+        assert_eq!(2, AnswerSheet::parse("[day1]\npart3 = 1").unwrap_err().line);
+
+        let sheet = AnswerSheet::parse("[day10]\npart1 = [\"a,b\", \"c\\\"\", ]\n").unwrap();
+        assert_eq!(
+            Some(&Answer::from(vec!["a,b".to_owned(), "c\"".to_owned()])),
+            sheet.get(10, Part::One)
+        );
+        for invalid in ["[\"a\" \"b\"]", "[\"a\",, \"b\"]", "[\"a]", "[a]"] {
+            let text = format!("[day10]\npart1 = {invalid}");
+            assert_eq!(2, AnswerSheet::parse(&text).unwrap_err().line, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn bench_statistics() {
+        let mut samples: Vec<_> = [5, 1, 4, 2, 3, 100, 6, 7, 8, 9]
+            .into_iter()
+            .map(Duration::from_micros)
+            .collect();
+        let stats = bench::Stats::from_samples(&mut samples);
+
+        assert_eq!(Duration::from_micros(1), stats.min);
+        assert_eq!(Duration::from_micros(5), stats.median);
+        assert_eq!(Duration::from_micros(100), stats.p95);
+        assert!(stats.mean.as_nanos().abs_diff(14_500) <= 1);
+    }
+
+    #[test]
+    fn bench_baseline_regressions() {
+        let stats = |micros| bench::Stats::from_samples(&mut [Duration::from_micros(micros)]);
+        let result = |parse, part1, part2| bench::DayBench {
+            day_no: 4,
+            phases: vec![
+                (bench::Phase::Parse, stats(parse)),
+                (bench::Phase::Part1, stats(part1)),
+                (bench::Phase::Part2, stats(part2)),
+            ],
+        };
+
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.csv", std::process::id()));
+        Baseline::from_results(&[result(100, 100, 100)])
+            .save(&path)
+            .unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let comparisons = baseline.compare(&[result(105, 150, 50)], 10);
+        let regressed: Vec<_> = comparisons.iter().map(|c| c.regressed).collect();
+        assert_eq!(vec![false, true, false], regressed);
+        assert!((comparisons[1].change - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn machine_readable_output() {
+        let solution = Solution {
+            day_no: 3,
+            t_input: Duration::from_nanos(1500),
+            part_1: Some(PartSolution {
+                answer: Ok(Answer::from("say \"hi\",\nbye")),
+                time: Duration::from_nanos(20),
+            }),
+            part_2: Some(PartSolution {
+                answer: Err(SolveError::new("no way out")),
+                time: Duration::from_nanos(30),
+            }),
+        };
+        let records = report::records(&solution, None);
+
+        let mut json = vec![];
+        report::write_json(&mut json, &records).unwrap();
+        assert_eq!(
+            "[\n  {\"day\": 3, \"part\": 1, \"answer\": \"say \\\"hi\\\",\\nbye\", \"parse_ns\": 1500, \"solve_ns\": 20},\n  {\"day\": 3, \"part\": 2, \"answer\": null, \"parse_ns\": 1500, \"solve_ns\": 30, \"error\": \"no way out\"}\n]\n",
+            String::from_utf8(json).unwrap()
+        );
+
+        let mut csv = vec![];
+        report::write_csv(&mut csv, &records).unwrap();
+        assert_eq!(
+            "day,part,answer,parse_ns,solve_ns,error\n3,1,\"say \"\"hi\"\",\nbye\",1500,20,\n3,2,,1500,30,no way out\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
 
-    fn verify_answers<D: Day>(answer_1: u64, answer_2: u64) {
-        let solution = calculate_solution::<D>();
+    #[test]
+    fn grid_navigation() {
+        use common::{geom::Point, grid::Grid};
+
+        let grid = Grid::parse("ab\ncd\nef\n", |c| c.is_ascii_lowercase().then_some(c)).unwrap();
+
+        assert_eq!((2, 3), (grid.width(), grid.height()));
+        assert_eq!(Some(&'d'), grid.get(Point::new(1, 1)));
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+        assert_eq!(
+            vec!['b', 'd', 'f'],
+            grid.column(1).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(&['c', 'd'], grid.row(1));
+        assert_eq!(
+            vec![Point::new(1, 0), Point::new(0, 1)],
+            grid.neighbours_4(Point::new(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(5, grid.neighbours_8(Point::new(1, 1)).count());
+        assert_eq!("AB\nCD\nEF", grid.map(char::to_ascii_uppercase).to_string());
+
+        let error = Grid::parse("ab\nc\n", Some).unwrap_err();
+        assert_eq!(
+            (2, "a row of 2 cells"),
+            (error.line, error.expected.as_str())
+        );
+        let error = Grid::parse("ab\nc!\n", |c| c.is_ascii_lowercase().then_some(c)).unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 2 is outside a grid 2 cells wide")]
+    fn grid_column_out_of_bounds() {
+        let grid = common::grid::Grid::parse("ab\ncd\n", Some).unwrap();
+        let _ = grid.column(2);
+    }
+
+    #[test]
+    fn geometry() {
+        use common::geom::{self, Direction, Point, Vector};
+
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 4));
+        assert_eq!(10, a.taxicab_distance(b));
+        assert_eq!(6, a.chebyshev_distance(b));
+        assert_eq!(Vector::new(-4, 6), b - a);
+        assert_eq!(Point::new(1, -3), a + Direction::Up);
+        assert_eq!(None, Point::new(i64::MAX, 0).checked_add(Vector::new(1, 0)));
+
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::DownLeft, Direction::DownRight.turn_right());
+        assert_eq!(Direction::UpLeft, Direction::UpRight.turn_left());
+        assert_eq!(Direction::Left, Direction::Right.opposite());
+
+        // Walking around a 2x3 rectangle gives twice its area.
+        let corners = [(0, 0), (2, 0), (2, 3), (0, 3), (0, 0)].map(|(x, y)| Point::new(x, y));
+        let area: i64 = corners.windows(2).map(|w| geom::shoelace(w[0], w[1])).sum();
+        assert_eq!(12, area.abs());
+    }
+
+    #[test]
+    fn integer_math() {
+        use common::math;
+
+        assert_eq!(6, math::gcd(54, 24));
+        assert_eq!(216, math::lcm(54, 24));
+        assert_eq!(4, math::gcd_all([8, 12, 20]));
+        assert_eq!(60, math::lcm_all([4, 6, 10, 15]));
+        assert_eq!(1, math::lcm_all([]));
 
-        assert_eq!(answer_1, solution.answer_1);
-        assert_eq!(answer_2, solution.answer_2);
+        let (g, x, y) = math::extended_gcd(240, 46);
+        assert_eq!(2, g);
+        assert_eq!(2, 240 * x + 46 * y);
+        assert_eq!(Some(4), math::mod_inverse(3, 11));
+        assert_eq!(None, math::mod_inverse(4, 8));
+
+        assert_eq!(Ok((23, 105)), math::crt([(2, 3), (3, 5), (2, 7)]));
+        // Moduli sharing a factor, as with Day08's cycles.
+        assert_eq!(Ok((10, 12)), math::crt([(4, 6), (2, 4)]));
+        assert_eq!(
+            Err(math::CrtError::Contradiction),
+            math::crt([(1, 4), (2, 6)])
+        );
+        assert_eq!(
+            Err(math::CrtError::Overflow),
+            math::crt([(1, i64::MAX), (2, i64::MAX - 1)])
+        );
     }
 
     #[test]
-    fn benchmark_all() {
+    #[should_panic(expected = "modulus must be positive, got 0")]
+    fn crt_with_zero_modulus() {
+        let _ = common::math::crt([(1, 3), (0, 0)]);
+    }
+
+    /// Starts an HTTP server on a random local port, which answers every request
+    /// with `response`. Returns its URL and the requests it receives.
+    fn stub_server(response: &'static str) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().map_while(Result::ok) {
+                let request: Vec<_> = BufReader::new(&stream)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|l| !l.is_empty())
+                    .collect();
+                sender.send(request.join("\n")).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    #[test]
+    fn fetch_inputs_once() {
+        let (url, requests) = stub_server("HTTP/1.0 200 OK\r\nContent-Length: 6\r\n\r\n1abc2\n");
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let inputs = InputSource::Directory(dir.clone());
+        let config = FetchConfig {
+            base_url: format!("{url}/2023/"),
+            session: "53cr3t".to_owned(),
+        };
+
+        let path = dir.join("day01.txt");
+        let fetched = inputs.fetch_day(1, &config).unwrap();
+        assert_eq!(Fetched::Downloaded(path.clone()), fetched);
+        assert_eq!("1abc2\n", std::fs::read_to_string(&path).unwrap());
+
+        let request = requests.try_recv().unwrap();
+        assert!(
+            request.starts_with("GET /2023/day/1/input HTTP/1.0\n"),
+            "{request}"
+        );
+        assert!(request.contains("\nCookie: session=53cr3t\n"), "{request}");
+
+        // The second time around, the input is served from the directory.
+        let fetched = inputs.fetch_day(1, &config).unwrap();
+        assert_eq!(Fetched::Cached(path), fetched);
+        assert!(requests.try_recv().is_err());
+
+        let (url, _requests) = stub_server("HTTP/1.0 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!");
+        let config = FetchConfig {
+            base_url: url,
+            ..config
+        };
+        let error = inputs.fetch_day(2, &config).unwrap_err();
+        assert!(
+            matches!(error, input::FetchError::Status(404, _)),
+            "{error}"
+        );
+        assert!(!dir.join("day02.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// A small pseudo-random number generator (SplitMix64), so randomised tests
+    /// are reproducible.
+    struct Rng(u64);
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        }
+
+        /// A number in `range`, which must not be empty.
+        fn range(&mut self, range: std::ops::Range<i64>) -> i64 {
+            range.start + (self.next() % (range.end - range.start) as u64) as i64
+        }
+    }
+
+    #[test]
+    fn interval_set_properties() {
+        use common::interval::{Interval, IntervalSet, OffsetMap};
+
+        const DOMAIN: std::ops::Range<i64> = -20..60;
+        let mut rng = Rng(5);
+        let random_intervals = |rng: &mut Rng| -> Vec<Interval> {
+            (0..rng.range(0..5))
+                .map(|_| Interval::from_start_len(rng.range(0..40), rng.range(0..8)))
+                .collect()
+        };
+
         for _ in 0..500 {
-            calculate_solution::<Day01>();
-            calculate_solution::<Day02>();
-            calculate_solution::<Day03>();
-            calculate_solution::<Day04>();
-            calculate_solution::<Day05>();
-            calculate_solution::<Day06>();
-            calculate_solution::<Day07>();
-            calculate_solution::<Day08>();
-            calculate_solution::<Day09>();
-            calculate_solution::<Day10>();
+            let a: IntervalSet = random_intervals(&mut rng).into_iter().collect();
+            let b: IntervalSet = random_intervals(&mut rng).into_iter().collect();
+            let check = |set: &IntervalSet, expected: &dyn Fn(i64) -> bool| {
+                for v in DOMAIN {
+                    assert_eq!(
+                        expected(v),
+                        set.contains(v),
+                        "{v} in {set:?}, from {a:?} and {b:?}"
+                    );
+                }
+                // Normalised sets are sorted, and neither overlap nor touch.
+                let intervals: Vec<_> = set.iter().collect();
+                assert!(intervals.iter().all(|i| !i.is_empty()));
+                assert!(intervals.windows(2).all(|w| w[0].end < w[1].start));
+            };
+
+            check(&a.union(&b), &|v| a.contains(v) || b.contains(v));
+            check(&a.intersection(&b), &|v| a.contains(v) && b.contains(v));
+            check(&a.difference(&b), &|v| a.contains(v) && !b.contains(v));
+
+            let boundaries: Vec<_> = (0..rng.range(0..4)).map(|_| rng.range(0..40)).collect();
+            let pieces = a.split_by(boundaries.iter().copied());
+            assert_eq!(a, pieces.iter().copied().collect());
+            for piece in &pieces {
+                assert!(!piece.is_empty());
+                assert!(!boundaries.iter().any(|&b| piece.start < b && b < piece.end));
+            }
+
+            let random_map = |rng: &mut Rng| {
+                let mut start = 0;
+                let pieces: Vec<_> = random_intervals(rng)
+                    .into_iter()
+                    .map(|i| {
+                        // Turn the random intervals into non-overlapping ones.
+                        start += i.start % 6;
+                        let piece = Interval::from_start_len(start, i.len() as i64);
+                        start = piece.end;
+                        (piece, rng.range(-10..10))
+                    })
+                    .collect();
+                OffsetMap::new(pieces).unwrap()
+            };
+            let map = random_map(&mut rng);
+            let next = random_map(&mut rng);
+
+            let expected: IntervalSet = DOMAIN
+                .filter(|&v| a.contains(v))
+                .map(|v| Interval::from_start_len(map.get(v), 1))
+                .collect();
+            assert_eq!(expected, map.apply(&a), "{a:?} through {map:?}");
+
+            let expected: IntervalSet = DOMAIN
+                .filter(|&v| b.contains(map.get(v)))
+                .map(|v| Interval::from_start_len(v, 1))
+                .collect();
+            assert_eq!(expected, map.preimage(&b), "{b:?} through {map:?}");
+
+            let composed = map.then(&next);
+            for v in DOMAIN {
+                assert_eq!(
+                    next.get(map.get(v)),
+                    composed.get(v),
+                    "{map:?} then {next:?}"
+                );
+            }
         }
+
+        let overlapping = [(Interval::new(0, 5), 1), (Interval::new(4, 6), 2)];
+        assert_eq!(None, OffsetMap::new(overlapping));
+    }
+
+    /// Includes a named example input from `src/input/examples`.
+    macro_rules! example {
+        ($name:literal) => {
+            include_str!(concat!("input/examples/", $name, ".txt"))
+        };
+    }
+
+    /// Marks a part that should not be checked for an example.
+    const SKIP: Option<i64> = None;
+
+    /// Parses an example input and verifies the answers of the parts for which
+    /// an answer is given.
+    fn verify_example<D: Day>(
+        input: &'static str,
+        answer_1: Option<impl Into<Answer>>,
+        answer_2: Option<impl Into<Answer>>,
+    ) {
+        let input = D::Input::load(input).unwrap_or_else(|e| panic!("{}", e.for_day(D::DAY_NO)));
+
+        if let Some(answer_1) = answer_1 {
+            assert_eq!(Ok(answer_1.into()), D::solve_challenge_1(&input), "part 1");
+        }
+        if let Some(answer_2) = answer_2 {
+            assert_eq!(Ok(answer_2.into()), D::solve_challenge_2(&input), "part 2");
+        }
+    }
+
+    #[test]
+    fn examples_day01() {
+        verify_example::<Day01>(example!("day01-calibration"), Some(142), SKIP);
+        verify_example::<Day01>(example!("day01-spelled-out"), SKIP, Some(281));
+    }
+
+    #[test]
+    fn day01_line_without_digits() {
+        let input = <Day01 as Day>::Input::load("a1b\nabc\n").unwrap();
+        let error = SolveError::new("line 2 has no digits to calibrate with");
+
+        assert_eq!(Err(error.clone()), Day01::solve_challenge_1(&input));
+        assert_eq!(Err(error), Day01::solve_challenge_2(&input));
+        // Neither long lines nor other scripts trip up the spelled out digits.
+        let input = <Day01 as Day>::Input::load("1234567890123456789\nöne2føur\n").unwrap();
+        assert_eq!(Ok(Answer::from(41_u64)), Day01::solve_challenge_2(&input));
+    }
+
+    #[test]
+    fn examples_day02() {
+        verify_example::<Day02>(example!("day02-example"), Some(8), Some(2286));
     }
 
     #[test]
-    pub fn test_day1() {
-        verify_answers::<Day01>(57346, 57345);
+    fn examples_day03() {
+        verify_example::<Day03>(example!("day03-example"), Some(4361), Some(467835));
     }
 
     #[test]
-    pub fn test_day2() {
-        verify_answers::<Day02>(2317, 74804);
+    fn examples_day04() {
+        verify_example::<Day04>(example!("day04-example"), Some(13), Some(30));
     }
 
     #[test]
-    pub fn test_day3() {
-        verify_answers::<Day03>(535078, 75312571);
+    fn examples_day05() {
+        verify_example::<Day05>(example!("day05-example"), Some(35), Some(46));
     }
 
     #[test]
-    pub fn test_day4() {
-        verify_answers::<Day04>(23235, 5920640);
+    fn day05_matches_brute_force() {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        let mut rng = Rng(1205);
+
+        for _ in 0..200 {
+            let seeds: Vec<i64> = (0..2 * rng.range(1..4))
+                .map(|i| {
+                    if i % 2 == 0 {
+                        rng.range(0..100)
+                    } else {
+                        rng.range(1..20)
+                    }
+                })
+                .collect();
+            // Every map gets a few ranges, with gaps between them, so seed ranges
+            // regularly straddle both their bounds.
+            let maps: Vec<Vec<(i64, i64, i64)>> = (0..7)
+                .map(|_| {
+                    let mut src = 0;
+                    (0..rng.range(1..5))
+                        .map(|_| {
+                            src += rng.range(0..15);
+                            let count = rng.range(1..30);
+                            let range = (rng.range(0..120), src, count);
+                            src += count;
+                            range
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let mut input = format!("seeds: {}\n", seeds.iter().join(" "));
+            for (names, ranges) in CATEGORIES.windows(2).zip(&maps) {
+                input += &format!("\n{}-to-{} map:\n", names[0], names[1]);
+                for (dest, src, count) in ranges {
+                    input += &format!("{dest} {src} {count}\n");
+                }
+            }
+
+            let locate = |seed: i64| {
+                maps.iter().fold(seed, |value, ranges| {
+                    ranges
+                        .iter()
+                        .find(|&&(_, src, count)| (src..src + count).contains(&value))
+                        .map_or(value, |&(dest, src, _)| value - src + dest)
+                })
+            };
+            let min_1 = seeds.iter().map(|&s| locate(s)).min();
+            let min_2 = seeds
+                .chunks_exact(2)
+                .flat_map(|c| c[0]..c[0] + c[1])
+                .map(locate)
+                .min();
+
+            verify_example::<Day05>(Box::leak(input.into_boxed_str()), min_1, min_2);
+        }
+    }
+
+    #[test]
+    fn trace_day05_stages() {
+        let almanac = <Day05 as Day>::Input::load(example!("day05-example")).unwrap();
+        let stages: Vec<_> = almanac
+            .trace(Interval::from_start_len(79, 1))
+            .iter()
+            .map(|s| format!("{} {}", s.category, s.values))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "seed 79",
+                "soil 81",
+                "fertilizer 81",
+                "water 81",
+                "light 74",
+                "temperature 78",
+                "humidity 78",
+                "location 82"
+            ],
+            stages
+        );
+
+        // Along the way, these seeds get split up into three ranges.
+        let location = almanac.trace(Interval::new(55, 68)).pop().unwrap();
+        assert_eq!("56..60, 86..90, 94..99", location.values.to_string());
+    }
+
+    #[test]
+    fn day05_seeds_landing_in_locations() {
+        let almanac = <Day05 as Day>::Input::load(example!("day05-example")).unwrap();
+
+        let seeds = almanac.seeds_landing_in(&Interval::from_start_len(82, 1).into());
+        assert!(seeds.contains(79));
+
+        // Check against planting every seed one by one.
+        let locations = common::interval::IntervalSet::from(Interval::new(40, 60));
+        let seeds = almanac.seeds_landing_in(&locations);
+        for seed in -10..150 {
+            let location = almanac
+                .trace(Interval::from_start_len(seed, 1))
+                .pop()
+                .and_then(|stage| stage.values.min())
+                .unwrap();
+            assert_eq!(locations.contains(location), seeds.contains(seed), "{seed}");
+        }
+    }
+
+    #[test]
+    fn day05_unpaired_or_empty_seeds() {
+        let part_2 = |seeds| {
+            let input = format!("seeds: {seeds}\n\nseed-to-soil map:\n50 98 2\n");
+            let almanac = <Day05 as Day>::Input::load(input.leak()).unwrap();
+            Day05::solve_challenge_2(&almanac).map_err(|e| e.to_string())
+        };
+
+        assert_eq!(
+            Err(
+                "the seeds should come in pairs of a start and a length, but there are 1 numbers"
+                    .to_owned()
+            ),
+            part_2("79")
+        );
+        assert_eq!(
+            Err("there are no seeds to plant".to_owned()),
+            part_2("79 0")
+        );
+        assert_eq!(Ok(Answer::from(80_u64)), part_2("79 0 80 3"));
+    }
+
+    #[test]
+    fn examples_day06() {
+        verify_example::<Day06>(example!("day06-example"), Some(288), Some(71503));
+    }
+
+    #[test]
+    fn examples_day07() {
+        verify_example::<Day07>(example!("day07-example"), Some(6440), Some(5905));
+    }
+
+    #[test]
+    fn examples_day08() {
+        verify_example::<Day08>(example!("day08-direct"), Some(2), Some(2));
+        verify_example::<Day08>(example!("day08-repeating"), Some(6), Some(6));
+        verify_example::<Day08>(example!("day08-ghosts"), SKIP, Some(6));
+        // Ghosts passing several end points per cycle, or only before entering it.
+        verify_example::<Day08>(example!("day08-offset-cycles"), SKIP, Some(7));
+        verify_example::<Day08>(example!("day08-lead-in"), SKIP, Some(1));
+    }
+
+    #[test]
+    fn day08_unreachable_end() {
+        let input =
+            "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, CCC)\nCCC = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = <Day08 as Day>::Input::load(input).unwrap();
+
+        assert_eq!(
+            Err(SolveError::new(
+                "the end is unreachable, the walk started repeating itself at BBB after 3 steps"
+            )),
+            Day08::solve_challenge_1(&input)
+        );
+    }
+
+    #[test]
+    fn day08_ghosts_that_never_meet() {
+        let never = |input| {
+            let input = <Day08 as Day>::Input::load(input).unwrap();
+            Day08::solve_challenge_2(&input).unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            "there are no starting points for the ghosts",
+            never("L\n\nAAB = (AAZ, AAZ)\nAAZ = (AAB, AAB)\n")
+        );
+        // One ghost is only ever at an end point after an odd number of steps,
+        // the other after an even number.
+        assert_eq!(
+            "the ghosts are never at the end points at the same time",
+            never("L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)\n")
+        );
+    }
+
+    #[test]
+    fn day08_ghosts_passing_many_end_points() {
+        // Every ghost is at an end point on every other step, and passes 31 of
+        // them before its cycle repeats.
+        let mut input = "L".repeat(31) + "\n\n";
+        for ghost in 1..=6 {
+            input.push_str(&format!(
+                "{ghost}{ghost}A = ({ghost}{ghost}B, {ghost}{ghost}B)\n\
+                 {ghost}{ghost}B = ({ghost}{ghost}Z, {ghost}{ghost}Z)\n\
+                 {ghost}{ghost}Z = ({ghost}{ghost}B, {ghost}{ghost}B)\n"
+            ));
+        }
+        verify_example::<Day08>(input.leak(), SKIP, Some(2));
+    }
+
+    #[test]
+    fn day08_missing_junction() {
+        let input = "L\n\nAAA = (QQQ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let error = <Day08 as Day>::Input::load(input).err().unwrap();
+
+        assert_eq!((3, 8), (error.line, error.column));
+        assert_eq!("a label with a junction, but QQQ has none", error.expected);
+    }
+
+    #[test]
+    fn day08_duplicate_junction() {
+        let input = "L\n\nAAA = (ZZZ, ZZZ)\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        let error = <Day08 as Day>::Input::load(input).err().unwrap();
+
+        assert_eq!((4, 1), (error.line, error.column));
+        assert_eq!(
+            "a label without a junction, but AAA already has one",
+            error.expected
+        );
+    }
+
+    #[test]
+    fn day08_too_many_junctions() {
+        let mut input = "L\n\n".to_owned();
+        for n in 0..=u16::MAX as u32 + 1 {
+            let [a, b, c] = [n / (64 * 64), n / 64 % 64, n % 64]
+                .map(|digit| char::from_u32(0x100 + digit).unwrap());
+            input.push_str(&format!("{a}{b}{c} = (AAA, AAA)\n"));
+        }
+        let error = <Day08 as Day>::Input::load(input.leak()).err().unwrap();
+
+        assert_eq!((u16::MAX as usize + 4, 1), (error.line, error.column));
+        assert_eq!("at most 65536 junctions", error.expected);
+    }
+
+    #[test]
+    fn day08_graph() {
+        let input =
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)\n";
+        let (instructions, map) = <Day08 as Day>::Input::load(input).unwrap();
+        let mut dot = vec![];
+        map.write_dot(&mut dot, Some(&instructions)).unwrap();
+
+        assert_eq!(
+            [
+                "digraph network {",
+                "    \"11A\" [style=filled, fillcolor=palegreen];",
+                "    \"11B\";",
+                "    \"11Z\" [style=filled, fillcolor=lightcoral];",
+                "    \"XXX\";",
+                "    \"11A\" -> \"11B\" [label=L];",
+                "    \"11A\" -> \"XXX\" [label=R];",
+                "    \"11B\" -> \"XXX\" [label=L];",
+                "    \"11B\" -> \"11Z\" [label=R, color=blue, penwidth=2];",
+                "    \"11Z\" -> \"11B\" [label=L, color=blue, penwidth=2];",
+                "    \"11Z\" -> \"XXX\" [label=R];",
+                "    \"XXX\" -> \"XXX\" [label=L];",
+                "    \"XXX\" -> \"XXX\" [label=R];",
+                "}",
+            ]
+            .join("\n")
+                + "\n",
+            String::from_utf8(dot).unwrap()
+        );
+    }
+
+    #[test]
+    fn examples_day09() {
+        verify_example::<Day09>(example!("day09-example"), Some(114), Some(2));
+    }
+
+    #[test]
+    fn examples_day10() {
+        verify_example::<Day10>(example!("day10-square"), Some(4), Some(1));
+        verify_example::<Day10>(example!("day10-winding"), Some(8), SKIP);
+        verify_example::<Day10>(example!("day10-enclosed"), SKIP, Some(4));
+        verify_example::<Day10>(example!("day10-larger"), SKIP, Some(8));
+        verify_example::<Day10>(example!("day10-junk"), SKIP, Some(10));
+    }
+
+    #[test]
+    fn visualize_day10_loop() {
+        let map = <Day10 as Day>::Input::load(example!("day10-junk")).unwrap();
+        let picture = map.visualize().unwrap().to_string();
+        let plain = regex::Regex::new("\x1b\\[[0-9;]*m")
+            .unwrap()
+            .replace_all(&picture, "");
+
+        assert_eq!(10, plain.matches('•').count());
+        assert_eq!(
+            vec![
+                "┌┌┐┌┐┌┐┌┐┌┐┌┐┌┐┌───┐",
+                "└│└┘││││││││││││┌──┘",
+                "┌└─┐└┘└┘││││││└┘└─┐┐",
+                "┌──┘┌──┐││└┘└┘•┌┐┌┘─",
+                "└───┘┌─┘└┘••••┌┘└┘┘┐",
+                "│┌│┌─┘┌───┐•••└┐└│┐│",
+                "│┌┌┘┌┐└┐┌─┘┌┐••└───┐",
+                "┐─└─┘└┐││┌┐│└┐┌─┐┌┐│",
+                "└ └┐└┌┘│││││┌┘└┐││└┘",
+                "└┐┘└┘└─┘└┘└┘└──┘└┘ └",
+            ],
+            plain.lines().collect_vec()
+        );
+        // All 160 loop tiles are highlighted, including the start tile, which is
+        // drawn as the pipe it stands for. Junk pipes are dimmed instead.
+        assert_eq!(159, picture.matches("\x1b[1;33m").count());
+        assert!(picture.contains("\x1b[1;31m┐\x1b[0m"));
+        assert!(picture.starts_with("\x1b[2m┌\x1b[0m"));
     }
 
     #[test]
-    pub fn test_day5() {
-        verify_answers::<Day05>(510109797, 9622622);
+    fn examples_day11() {
+        verify_example::<Day11>(example!("day11-example"), Some(374), Some(82000210));
     }
 
     #[test]
-    pub fn test_day6() {
-        verify_answers::<Day06>(2374848, 39132886);
+    fn examples_day12() {
+        verify_example::<Day12>(example!("day12-example"), Some(21), Some(525152));
     }
 }
@@ -0,0 +1,49 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use crate::{
+    input::{InputSource, LoadError},
+    registry::DynDay,
+    Solution, SolveConfig,
+};
+
+/// Solves the days on a pool of worker threads. The results are returned in the
+/// same order as the days.
+pub fn solve_days(
+    days: &[&dyn DynDay],
+    inputs: &InputSource,
+    config: SolveConfig,
+) -> Vec<Result<Solution, LoadError>> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(days.len());
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next_day = &next_day;
+            scope.spawn(move || {
+                // Each worker keeps picking up the next unsolved day until none are left.
+                loop {
+                    let idx = next_day.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(idx) else {
+                        break;
+                    };
+                    sender.send((idx, day.solve(inputs, config))).unwrap();
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<_> = receiver.into_iter().collect();
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
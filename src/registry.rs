@@ -0,0 +1,54 @@
+use crate::{
+    bench::{self, BenchConfig, DayBench},
+    calculate_solution,
+    input::{InputSource, LoadError},
+    Day, Solution, SolveConfig,
+};
+
+/// Object-safe view of a [`Day`], allowing solutions to be stored and
+/// looked up at runtime.
+pub trait DynDay: Sync {
+    fn day_no(&self) -> usize;
+    fn solve(&self, inputs: &InputSource, config: SolveConfig) -> Result<Solution, LoadError>;
+    fn bench(&self, inputs: &InputSource, config: BenchConfig) -> Result<DayBench, LoadError>;
+}
+
+impl<D: Day + Sync> DynDay for D {
+    fn day_no(&self) -> usize {
+        D::DAY_NO
+    }
+
+    fn solve(&self, inputs: &InputSource, config: SolveConfig) -> Result<Solution, LoadError> {
+        calculate_solution::<D>(inputs, config)
+    }
+
+    fn bench(&self, inputs: &InputSource, config: BenchConfig) -> Result<DayBench, LoadError> {
+        bench::bench_day::<D>(inputs, config)
+    }
+}
+
+pub struct Registry {
+    days: &'static [&'static dyn DynDay],
+}
+impl Registry {
+    /// Creates a new registry. The days must be listed in ascending order.
+    pub const fn new(days: &'static [&'static dyn DynDay]) -> Self {
+        Self { days }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn DynDay> + '_ {
+        self.days.iter().copied()
+    }
+
+    pub fn get(&self, day_no: usize) -> Option<&'static dyn DynDay> {
+        self.iter().find(|d| d.day_no() == day_no)
+    }
+
+    pub fn first_day(&self) -> usize {
+        self.days[0].day_no()
+    }
+
+    pub fn last_day(&self) -> usize {
+        self.days[self.days.len() - 1].day_no()
+    }
+}
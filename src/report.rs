@@ -0,0 +1,148 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::{
+    answer::{Answer, AnswerSheet, SolveError},
+    check::{self, Verdict},
+    cli::Part,
+    Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// A single row of machine-readable output, describing one part of a day.
+pub struct Record<'s> {
+    pub day_no: usize,
+    pub part: Part,
+    pub answer: Result<&'s Answer, &'s SolveError>,
+    pub t_parse: Duration,
+    pub t_solve: Duration,
+    pub verdict: Option<&'static str>,
+}
+
+pub fn records<'s>(solution: &'s Solution, sheet: Option<&'s AnswerSheet>) -> Vec<Record<'s>> {
+    let verdicts: Vec<_> = match sheet {
+        Some(sheet) => check::check_solution(sheet, solution)
+            .into_iter()
+            .map(|c| Some(verdict_name(&c.verdict)))
+            .collect(),
+        None => solution.parts().map(|_| None).collect(),
+    };
+
+    solution
+        .parts()
+        .zip(verdicts)
+        .map(|((part, p), verdict)| Record {
+            day_no: solution.day_no,
+            part,
+            answer: p.answer.as_ref(),
+            t_parse: solution.t_input,
+            t_solve: p.time,
+            verdict,
+        })
+        .collect()
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail { .. } => "fail",
+        Verdict::Unknown { .. } => "unknown",
+        Verdict::Error(_) => "error",
+    }
+}
+
+/// Writes the records as a JSON array. Answers are always written as strings,
+/// since large integers cannot be represented exactly by every JSON consumer.
+/// Parts that could not be solved have a `null` answer and an error instead.
+pub fn write_json<W: Write>(mut w: W, records: &[Record]) -> io::Result<()> {
+    writeln!(w, "[")?;
+    for (idx, r) in records.iter().enumerate() {
+        let answer = match r.answer {
+            Ok(answer) => json_string(&answer.to_string()),
+            Err(_) => "null".to_owned(),
+        };
+        write!(
+            w,
+            "  {{\"day\": {}, \"part\": {}, \"answer\": {answer}, \"parse_ns\": {}, \"solve_ns\": {}",
+            r.day_no,
+            r.part.number(),
+            r.t_parse.as_nanos(),
+            r.t_solve.as_nanos()
+        )?;
+        if let Err(e) = r.answer {
+            write!(w, ", \"error\": {}", json_string(&e.to_string()))?;
+        }
+        if let Some(verdict) = r.verdict {
+            write!(w, ", \"check\": {}", json_string(verdict))?;
+        }
+        let separator = if idx + 1 < records.len() { "," } else { "" };
+        writeln!(w, "}}{separator}")?;
+    }
+    writeln!(w, "]")
+}
+
+/// Writes the records as CSV. The answer of a part that could not be solved is
+/// left empty, and the reason is given in an extra error column.
+pub fn write_csv<W: Write>(mut w: W, records: &[Record]) -> io::Result<()> {
+    let with_check = records.iter().any(|r| r.verdict.is_some());
+    let with_error = records.iter().any(|r| r.answer.is_err());
+
+    write!(w, "day,part,answer,parse_ns,solve_ns")?;
+    write!(w, "{}", if with_check { ",check" } else { "" })?;
+    writeln!(w, "{}", if with_error { ",error" } else { "" })?;
+    for r in records {
+        let answer = r.answer.map(|a| a.to_string()).unwrap_or_default();
+        write!(
+            w,
+            "{},{},{},{},{}",
+            r.day_no,
+            r.part.number(),
+            csv_field(&answer),
+            r.t_parse.as_nanos(),
+            r.t_solve.as_nanos()
+        )?;
+        if with_check {
+            write!(w, ",{}", r.verdict.unwrap_or_default())?;
+        }
+        if with_error {
+            let error = r.answer.err().map(|e| e.to_string()).unwrap_or_default();
+            write!(w, ",{}", csv_field(&error))?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
@@ -1,6 +1,7 @@
-use arrayvec::ArrayVec;
-
-use crate::Day;
+use crate::{
+    answer::{Answer, SolveError},
+    Day,
+};
 
 pub struct Day01;
 impl Day for Day01 {
@@ -8,22 +9,35 @@ impl Day for Day01 {
 
     const DAY_NO: usize = 1;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input.iter().map(|&l| calibrate(l)).sum()
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        sum_calibrations(input, calibrate)
+    }
+
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        sum_calibrations(input, calibrate_spelled_out)
     }
+}
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input.iter().map(|&l| calibrate_spelled_out(l)).sum()
+fn sum_calibrations(
+    lines: &[&str],
+    calibrate: fn(&str) -> Option<u64>,
+) -> Result<Answer, SolveError> {
+    let mut sum = 0;
+    for (idx, line) in lines.iter().enumerate() {
+        sum += calibrate(line).ok_or_else(|| {
+            SolveError::new(format!("line {} has no digits to calibrate with", idx + 1))
+        })?;
     }
+    Ok(sum.into())
 }
 
-fn calibrate(line: &str) -> u64 {
+fn calibrate(line: &str) -> Option<u64> {
     let digits = line.chars().filter_map(|c| c.to_digit(10).map(|d| d as u8));
 
     calculate_calibration(digits)
 }
 
-fn calibrate_spelled_out(line: &str) -> u64 {
+fn calibrate_spelled_out(line: &str) -> Option<u64> {
     let replacements = [
         ("one", 1),
         ("two", 2),
@@ -36,25 +50,23 @@ fn calibrate_spelled_out(line: &str) -> u64 {
         ("nine", 9),
     ];
 
-    let mut digits: ArrayVec<u8, 16> = ArrayVec::new();
-
-    for idx in 0..line.len() {
+    let digits = line.char_indices().filter_map(|(idx, c)| {
         let slice = &line[idx..];
 
         if let Some((_, rep)) = replacements.iter().find(|(n, _)| slice.starts_with(n)) {
-            digits.push(*rep);
-        } else if let Some(d) = slice.chars().next().and_then(|c| c.to_digit(10)) {
-            digits.push(d as u8);
+            Some(*rep)
+        } else {
+            c.to_digit(10).map(|d| d as u8)
         }
-    }
+    });
 
-    calculate_calibration(digits.into_iter())
+    calculate_calibration(digits)
 }
 
-fn calculate_calibration<I: DoubleEndedIterator<Item = u8> + Clone>(mut iter: I) -> u64 {
-    let first = iter.next().unwrap();
+fn calculate_calibration<I: DoubleEndedIterator<Item = u8>>(mut iter: I) -> Option<u64> {
+    let first = iter.next()?;
     let last = iter.next_back().unwrap_or(first) as u64;
     let first = first as u64;
 
-    first * 10 + last
+    Some(first * 10 + last)
 }
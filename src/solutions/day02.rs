@@ -1,12 +1,18 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
+    combinator::cut,
     multi::separated_list1,
     sequence::{delimited, separated_pair},
     IResult,
 };
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day02;
 impl Day for Day02 {
@@ -14,22 +20,27 @@ impl Day for Day02 {
 
     const DAY_NO: usize = 2;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
         let max_draw = Draw {
             red: 12,
             green: 13,
             blue: 14,
         };
 
-        input
+        Ok(input
             .iter()
             .filter(|g| g.is_possible(&max_draw))
             .map(|g| g.game_no as u64)
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input.iter().map(|g| g.required_draw().power()).sum()
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
+            .iter()
+            .map(|g| g.required_draw().power())
+            .sum::<u64>()
+            .into())
     }
 }
 
@@ -76,7 +87,7 @@ impl Draw {
 }
 
 impl DayInput for Game {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(game, input)
     }
 }
@@ -89,7 +100,7 @@ fn game(i: &str) -> IResult<&str, Game> {
 }
 
 fn draw(i: &str) -> nom::IResult<&str, Draw> {
-    let (i, colors) = separated_list1(tag(", "), color)(i)?;
+    let (i, colors) = separated_list1(tag(", "), cut(color))(i)?;
 
     let mut draw: Draw = Default::default();
 
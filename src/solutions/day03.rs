@@ -2,7 +2,12 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::{input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common::{geom::Point, grid::Grid},
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day03;
 impl Day for Day03 {
@@ -10,60 +15,56 @@ impl Day for Day03 {
 
     const DAY_NO: usize = 3;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
             .numbers
             .iter()
             .filter(|n| n.is_adjacent_to_symbol(input))
             .map(|i| i.value as u64)
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
         let number_lookup = build_number_lookup(&input.numbers);
 
-        input
+        Ok(input
             .find_gears()
             .map(|gear| input.numbers_around(gear, &number_lookup))
             .filter(|nums| nums.len() == 2)
             .map(|nums| nums.iter().map(|n| n.value as u64).product::<u64>())
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 }
 
 fn build_number_lookup(numbers: &[Number]) -> HashMap<Point, &Number> {
     let mut m = HashMap::with_capacity(4096);
-    m.extend(
-        numbers
-            .iter()
-            .flat_map(|n| n.area().walk_lr_tb().map(move |p| (p, n))),
-    );
+    m.extend(numbers.iter().flat_map(|n| n.points().map(move |p| (p, n))));
     m
 }
 
 pub struct Schematic {
-    rows: Vec<Vec<Character>>,
+    grid: Grid<Character>,
     numbers: Vec<Number>,
 }
 impl Schematic {
-    fn new(rows: Vec<Vec<Character>>) -> Self {
+    fn new(grid: Grid<Character>) -> Self {
         let mut numbers = Vec::with_capacity(1536);
         numbers.extend(
-            rows.iter()
+            grid.rows()
                 .enumerate()
                 .flat_map(|(y, row)| NumbersBuilder::build(row, y)),
         );
 
-        Self { rows, numbers }
+        Self { grid, numbers }
     }
 
     fn find_gears(&self) -> impl Iterator<Item = Point> + '_ {
-        self.enumerate_points()
-            .filter(|p| self.lookup(*p).is_gear())
-    }
-
-    fn enumerate_points(&self) -> impl Iterator<Item = Point> {
-        Area::new(Point::new(0, 0), self.right_bottom()).walk_lr_tb()
+        self.grid
+            .iter()
+            .filter(|(_, c)| c.is_gear())
+            .map(|(p, _)| p)
     }
 
     fn numbers_around<'n>(
@@ -71,31 +72,13 @@ impl Schematic {
         point: Point,
         lookup: &HashMap<Point, &'n Number>,
     ) -> Vec<&'n Number> {
-        point
-            .area()
-            .expand_one(self.right_bottom())
-            .walk_lr_tb()
+        self.grid
+            .neighbours_8(point)
             .flat_map(|p| lookup.get(&p))
             .unique_by(|n| n.origin)
             .copied()
             .collect()
     }
-
-    fn lookup(&self, point: Point) -> &Character {
-        &self.rows[point.y][point.x]
-    }
-
-    fn width(&self) -> usize {
-        self.rows[0].len()
-    }
-
-    fn height(&self) -> usize {
-        self.rows.len()
-    }
-
-    fn right_bottom(&self) -> Point {
-        Point::new(self.width() - 1, self.height() - 1)
-    }
 }
 
 struct NumbersBuilder<'r> {
@@ -129,7 +112,7 @@ impl<'r> NumbersBuilder<'r> {
 
     fn get_or_create_number(&mut self, x: usize) -> &mut Number {
         self.current.get_or_insert(Number {
-            origin: Point::new(x, self.y),
+            origin: Point::new(x as i64, self.y as i64),
             length: 0,
             value: 0,
         })
@@ -153,18 +136,15 @@ impl Number {
     }
 
     fn is_adjacent_to_symbol(&self, schematic: &Schematic) -> bool {
-        self.area()
-            .expand_one(schematic.right_bottom())
-            .walk_lr_tb()
-            .map(|p| schematic.lookup(p))
-            .any(|n| n.is_symbol())
+        self.points()
+            .flat_map(|p| schematic.grid.neighbours_8(p))
+            .any(|p| schematic.grid[p].is_symbol())
     }
 
-    fn area(&self) -> Area {
-        Area::new(
-            self.origin,
-            Point::new(self.origin.x + self.length - 1, self.origin.y),
-        )
+    /// The points covered by the digits of this number.
+    fn points(&self) -> impl Iterator<Item = Point> {
+        let Point { x, y } = self.origin;
+        (x..x + self.length as i64).map(move |x| Point::new(x, y))
     }
 }
 
@@ -184,65 +164,9 @@ impl Character {
     }
 }
 
-pub struct Area {
-    left_top: Point,
-    right_bottom: Point,
-}
-impl Area {
-    fn new(left_top: Point, right_bottom: Point) -> Self {
-        Self {
-            left_top,
-            right_bottom,
-        }
-    }
-
-    /// Walk the area from left to right, top to bottom
-    fn walk_lr_tb(&self) -> impl Iterator<Item = Point> {
-        let y_range = self.left_top.y..=self.right_bottom.y;
-        let x_range = self.left_top.x..=self.right_bottom.x;
-
-        y_range.flat_map(move |y| x_range.clone().map(move |x| Point::new(x, y)))
-    }
-
-    /// Creates a new area, expanded by 1 point, keeping it within the bounds of
-    /// `origin..=right_bottom_bound`
-    fn expand_one(&self, right_bottom_bound: Point) -> Area {
-        let min = Point::new(
-            self.left_top.x.saturating_sub(1),
-            self.left_top.y.saturating_sub(1),
-        );
-        let max = Point::new(
-            (self.right_bottom.x + 1).min(right_bottom_bound.x),
-            (self.right_bottom.y + 1).min(right_bottom_bound.y),
-        );
-        Area::new(min, max)
-    }
-}
-
-#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
-pub struct Point {
-    x: usize,
-    y: usize,
-}
-impl Point {
-    fn new(x: usize, y: usize) -> Self {
-        Self { x, y }
-    }
-
-    fn area(&self) -> Area {
-        Area::new(*self, *self)
-    }
-}
-
 impl DayInput for Schematic {
-    fn load(input: &'static str) -> Self {
-        Schematic::new(Vec::load(input))
-    }
-}
-
-impl DayInput for Vec<Character> {
-    fn load(input: &'static str) -> Self {
-        input.chars().map(Character::from_char).collect()
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        Grid::parse(input, |c| Some(Character::from_char(c))).map(Schematic::new)
     }
 }
 
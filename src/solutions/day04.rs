@@ -1,13 +1,19 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, multispace1},
+    combinator::cut,
     multi::{fold_many1, many1},
     sequence::{delimited, preceded, separated_pair, terminated},
     IResult,
 };
 use rustc_hash::FxHashSet;
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day04;
 impl Day for Day04 {
@@ -15,14 +21,14 @@ impl Day for Day04 {
 
     const DAY_NO: usize = 4;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        Collection::new(input).score()
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(Collection::new(input).score().into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
         let mut collection = Collection::new(input);
         collection.redeem_all();
-        collection.copies.iter().sum()
+        Ok(collection.copies.iter().sum::<u64>().into())
     }
 }
 
@@ -88,7 +94,7 @@ impl Card {
 }
 
 impl DayInput for Vec<Card> {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(cards, input)
     }
 }
@@ -106,14 +112,14 @@ fn card<'i, 'b>(
     have: &'b mut FxHashSet<u32>,
 ) -> IResult<&'i str, Card> {
     let (i, card_no) = delimited(tag("Card"), whitespace_number, tag(":"))(i)?;
-    let (i, _) = terminated(
+    let (i, _) = cut(terminated(
         separated_pair(
             |i| number_set(i, winning),
             tag(" |"),
             |i| number_set(i, have),
         ),
         line_ending,
-    )(i)?;
+    ))(i)?;
 
     let card = Card::new(card_no as usize, winning, have);
     Ok((i, card))
@@ -1,14 +1,21 @@
-use std::cmp::Ordering;
-
 use nom::{
-    bytes::complete::{tag, take_until1},
-    character::complete::line_ending,
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending},
+    combinator::{cut, map_opt},
     multi::many1,
-    sequence::{delimited, preceded, terminated},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
     IResult,
 };
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common::{
+        self,
+        interval::{Interval, IntervalSet, OffsetMap},
+    },
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day05;
 impl Day for Day05 {
@@ -16,196 +23,143 @@ impl Day for Day05 {
 
     const DAY_NO: usize = 5;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input.find_min_location(|s| s.iter().map(|s| SeedCollection::from_start_count(*s, 1)))
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let seeds = input.seeds.iter().map(|&s| Interval::from_start_len(s, 1));
+        Ok(input.find_min_location(seeds)?.into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input.find_min_location(|s| {
-            s.chunks_exact(2)
-                .map(|c| SeedCollection::from_start_count(c[0], c[1]))
-        })
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        if input.seeds.len() % 2 != 0 {
+            return Err(SolveError::new(format!(
+                "the seeds should come in pairs of a start and a length, but there are {} numbers",
+                input.seeds.len()
+            )));
+        }
+
+        let seeds = input
+            .seeds
+            .chunks_exact(2)
+            .map(|c| Interval::from_start_len(c[0], c[1]));
+        Ok(input.find_min_location(seeds)?.into())
     }
 }
 
 pub struct Almanac {
-    seeds: Vec<u64>,
+    seeds: Vec<i64>,
     range_maps: Vec<RangeMap>,
+    /// All range maps composed into one, which takes a seed straight to its
+    /// location.
+    seed_to_location: OffsetMap,
 }
 impl Almanac {
-    /// Given a seed builder, find the lowest location where any of the seeds end
-    /// up being planted.
-    fn find_min_location<'s, F: FnOnce(&'s [u64]) -> I, I: Iterator<Item = SeedCollection>>(
-        &'s self,
-        seed_builder: F,
-    ) -> u64 {
-        seed_builder(&self.seeds)
-            .flat_map(|seeds| self.cascade(seeds))
-            .map(|seeds| seeds.start)
+    /// Finds the lowest location where any of the seeds end up being planted.
+    fn find_min_location(
+        &self,
+        seeds: impl IntoIterator<Item = Interval>,
+    ) -> Result<i64, SolveError> {
+        self.seed_to_location
+            .apply(&seeds.into_iter().collect())
             .min()
-            .unwrap()
+            .ok_or_else(|| SolveError::new("there are no seeds to plant"))
     }
 
-    /// Cascade a seed collection through multiple range maps.
-    /// Each range map may split up the collection into multiple smaller collections.
-    fn cascade(&self, collection: SeedCollection) -> Vec<SeedCollection> {
-        self.range_maps
-            .iter()
-            .fold(vec![collection], |collections, map| {
-                collections
-                    .into_iter()
-                    .flat_map(|r| map.transform(r))
-                    .collect()
-            })
-    }
-}
-
-#[derive(Clone, Copy)]
-pub struct SeedCollection {
-    start: u64, // Inclusive
-    end: u64,   // Exclusive
-}
-impl SeedCollection {
-    pub fn from_start_count(start: u64, count: u64) -> SeedCollection {
-        Self {
-            start,
-            end: start + count,
-        }
+    /// Finds every seed that ends up being planted in one of the locations.
+    pub fn seeds_landing_in(&self, locations: &IntervalSet) -> IntervalSet {
+        self.seed_to_location.preimage(locations)
     }
 
-    pub fn from_first_last(first: u64, last: u64) -> SeedCollection {
-        Self {
-            start: first,
-            end: last + 1,
+    /// Follows a range of seeds through every map, listing the values they
+    /// correspond to in each category, starting with the seeds themselves.
+    pub fn trace(&self, seeds: Interval) -> Vec<Stage<'_>> {
+        let first = Stage {
+            category: self.range_maps.first().map_or("seed", |m| &m.source),
+            values: seeds.into(),
+        };
+
+        let mut stages = vec![first];
+        for map in &self.range_maps {
+            let values = map.offsets.apply(&stages[stages.len() - 1].values);
+            stages.push(Stage {
+                category: &map.destination,
+                values,
+            });
         }
+        stages
     }
 }
 
-struct RangeMap {
-    ranges: Vec<Range>,
-}
-impl RangeMap {
-    fn new(mut ranges: Vec<Range>) -> Self {
-        ranges.sort_by_key(|r| r.source_start);
-
-        Self { ranges }
-    }
-
-    fn transform(&self, seed_collection: SeedCollection) -> Vec<SeedCollection> {
-        // Several ranges may be able to transform (part of) the seed collection.
-        // Find out where they are.
-        let first_idx = self
-            .find_range_index(seed_collection.start)
-            .unwrap_or_else(|b| b);
-        let last_idx = self
-            .find_range_index(seed_collection.end - 1)
-            .map(|i| i + 1)
-            .unwrap_or_else(|b| b.min(self.ranges.len()));
-
-        self.ranges[first_idx..last_idx]
-            .iter()
-            .map(|r| r.translate_collection(seed_collection))
-            .collect()
-    }
-
-    fn find_range_index(&self, source: u64) -> Result<usize, usize> {
-        // This is where the magic happens.
-        self.ranges
-            .binary_search_by(|candidate| candidate.cmp(source))
-    }
-}
-
-#[derive(Debug)]
-struct Range {
-    source_start: u64,
-    source_end: u64,
-    offset: i64,
+pub struct Stage<'a> {
+    pub category: &'a str,
+    pub values: IntervalSet,
 }
-impl Range {
-    fn new(src: u64, dest: u64, count: u64) -> Range {
-        let offset = (dest as i64) - (src as i64);
-
-        Self {
-            source_start: src,
-            source_end: src + count,
-            offset,
-        }
-    }
-
-    pub fn translate(&self, src: u64) -> u64 {
-        (src as i64 + self.offset) as u64
-    }
-
-    pub fn cmp(&self, value: u64) -> Ordering {
-        match value {
-            v if v < self.source_start => Ordering::Greater, // self > value
-            v if v >= self.source_end => Ordering::Less,     // self < value
-            _ => Ordering::Equal,
-        }
-    }
 
-    fn translate_collection(&self, seed_collection: SeedCollection) -> SeedCollection {
-        // This implementation works in practice, but for correctness,
-        // it should really return 0-3 seed collections:
-        // 0 if there is no overlap whatsoever;
-        // 1 fully translated collection if the seed collection is entirely
-        //   contained within the range;
-        // 2 collections (a translated part and an untranslated part) if the
-        //   seed collection is partially outside one bound of the range;
-        // 3 collections (one translated part and two untranslated parts) if the
-        //   seed collection is partially outside both bounds of the range.
-        let start = seed_collection.start.max(self.source_start);
-        let end = seed_collection.end.min(self.source_end);
-
-        if start < end {
-            let first = start;
-            let last = end - 1;
-            let first_to_dest = self.translate(first);
-            let last_to_dest = self.translate(last);
-
-            SeedCollection::from_first_last(first_to_dest, last_to_dest)
-        } else {
-            seed_collection
-        }
-    }
+/// Maps values from one category (e.g. seeds) to another (e.g. soil).
+struct RangeMap {
+    source: String,
+    destination: String,
+    offsets: OffsetMap,
 }
 
 impl DayInput for Almanac {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(almanac, input)
     }
 }
 
-fn almanac(i: &'static str) -> IResult<&str, Almanac> {
+fn almanac(i: &str) -> IResult<&str, Almanac> {
     let (i, seeds) = seed_list(i)?;
-    let (i, maps) = many1(range_map)(i)?;
+    let (i, range_maps) = many1(range_map)(i)?;
+
+    let seed_to_location = range_maps
+        .iter()
+        .fold(OffsetMap::default(), |composed, map| {
+            composed.then(&map.offsets)
+        });
 
     Ok((
         i,
         Almanac {
             seeds,
-            range_maps: maps,
+            range_maps,
+            seed_to_location,
         },
     ))
 }
 
-fn range_map(i: &'static str) -> IResult<&str, RangeMap> {
-    let (i, _) = delimited(eol, take_until1("\n"), eol)(i)?;
-    let (i, ranges) = many1(range)(i)?;
+fn range_map(i: &str) -> IResult<&str, RangeMap> {
+    let (i, (source, destination)) = delimited(
+        eol,
+        cut(separated_pair(alpha1, tag("-to-"), alpha1)),
+        cut(pair(tag(" map:"), eol)),
+    )(i)?;
+    let (i, offsets) = map_opt(many1(range), OffsetMap::new)(i)?;
 
-    Ok((i, RangeMap::new(ranges)))
+    Ok((
+        i,
+        RangeMap {
+            source: source.to_owned(),
+            destination: destination.to_owned(),
+            offsets,
+        },
+    ))
 }
 
-fn range(i: &str) -> IResult<&str, Range> {
-    let (i, dest) = terminated(common::u64, tag(" "))(i)?;
-    let (i, src) = terminated(common::u64, tag(" "))(i)?;
-    let (i, count) = terminated(common::u64, eol)(i)?;
+/// Parses a `destination source length` line into the source range and the
+/// offset that moves it to its destination.
+fn range(i: &str) -> IResult<&str, (Interval, i64)> {
+    let (i, dest) = terminated(common::i64, tag(" "))(i)?;
+    let (i, src) = cut(terminated(common::i64, tag(" ")))(i)?;
+    let (i, count) = cut(terminated(common::i64, eol))(i)?;
 
-    Ok((i, Range::new(src, dest, count)))
+    Ok((i, (Interval::from_start_len(src, count), dest - src)))
 }
 
-fn seed_list(i: &str) -> IResult<&str, Vec<u64>> {
-    delimited(tag("seeds:"), many1(preceded(tag(" "), common::u64)), eol)(i)
+fn seed_list(i: &str) -> IResult<&str, Vec<i64>> {
+    delimited(
+        tag("seeds:"),
+        many1(preceded(tag(" "), cut(common::i64))),
+        eol,
+    )(i)
 }
 
 fn eol(i: &str) -> IResult<&str, &str> {
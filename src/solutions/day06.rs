@@ -6,7 +6,12 @@ use nom::{
     IResult,
 };
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day06;
 impl Day for Day06 {
@@ -14,12 +19,17 @@ impl Day for Day06 {
 
     const DAY_NO: usize = 6;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input.races_incorrect.iter().map(Race::solve).product()
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
+            .races_incorrect
+            .iter()
+            .map(Race::solve)
+            .product::<u64>()
+            .into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input.race.solve()
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.race.solve().into())
     }
 }
 
@@ -66,13 +76,17 @@ impl Race {
         let lower_bound = (-d + discriminant) / -2.0;
         let upper_bound = (-d - discriminant) / -2.0;
 
-        let solution_count = upper_bound.ceil() - lower_bound.ceil();
+        // Only hold times strictly between the bounds beat the record. If a bound
+        // is a whole number, that hold time merely ties the record.
+        let first = lower_bound.floor() + 1.0;
+        let last = upper_bound.ceil() - 1.0;
+        let solution_count = last - first + 1.0;
         solution_count as u64
     }
 }
 
 impl DayInput for Competition {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(competition, input)
     }
 }
@@ -9,7 +9,12 @@ use nom::{
     IResult,
 };
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day07;
 impl Day for Day07 {
@@ -17,24 +22,26 @@ impl Day for Day07 {
 
     const DAY_NO: usize = 7;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
             .iter()
             .sorted_by_key(|h| (h.rank, h.cards))
             .copied()
             .enumerate()
             .map(|s| s.score())
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
             .iter()
             .map(|h| (*h, h.as_wild_card().promote()))
             .sorted_by_key(|(orig, promoted)| (promoted.rank, orig.cards))
             .enumerate()
             .map(|(idx, (hand, _))| (idx, hand).score())
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 }
 
@@ -165,7 +172,7 @@ enum WildCard {
 }
 
 impl DayInput for Hand<ClassicCard> {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(hand, input)
     }
 }
@@ -1,14 +1,28 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::{complete::anychar, streaming::line_ending},
-    combinator::{map, map_opt},
+    combinator::{consumed, cut, map, map_opt},
     multi::{count, many1},
     sequence::{delimited, pair, terminated, tuple},
     IResult,
 };
 use rustc_hash::FxHashMap;
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common::{
+        self,
+        math::{self, CrtError},
+    },
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day08;
 impl Day for Day08 {
@@ -16,82 +30,408 @@ impl Day for Day08 {
 
     const DAY_NO: usize = 8;
 
-    fn solve_challenge_1((instrs, map): &Self::Input) -> u64 {
-        let start = Label(['A', 'A', 'A']);
-        let end = Label(['Z', 'Z', 'Z']);
-        let instruction_cycle = instrs.iter().copied().cycle();
-        Cursor::new(start, map, instruction_cycle).walk_to(|l| l == end)
+    fn solve_challenge_1((instrs, map): &Self::Input) -> Result<Answer, SolveError> {
+        let node = |label| {
+            map.find(label)
+                .ok_or_else(|| SolveError::new(format!("there is no junction for {label}")))
+        };
+        let (start, end) = (node(Label(['A', 'A', 'A']))?, node(Label(['Z', 'Z', 'Z']))?);
+        let steps = Cursor::new(start, map, instrs)
+            .walk_to(|n| n == end)
+            .map_err(SolveError::new)?;
+        Ok(steps.into())
     }
 
-    fn solve_challenge_2((instrs, map): &Self::Input) -> u64 {
-        map.starting_points()
-            .map(|start| {
-                let instruction_cycle = instrs.iter().copied().cycle();
-                Cursor::new(start, map, instruction_cycle).walk_to(|l| l.ends_with('Z'))
-            })
-            .reduce(least_common_multiple)
-            .unwrap()
+    fn solve_challenge_2((instrs, map): &Self::Input) -> Result<Answer, SolveError> {
+        let ghosts: Vec<_> = map
+            .starting_points()
+            .map(|start| Ghost::track(Cursor::new(start, map, instrs)))
+            .collect();
+
+        let steps = first_meeting(&ghosts).map_err(SolveError::new)?;
+        Ok(steps.into())
     }
 }
 
-fn least_common_multiple(left: u64, right: u64) -> u64 {
-    let max = left.max(right);
-    let min = left.min(right);
+/// Finds the first step count at which all ghosts are at an end point.
+fn first_meeting(ghosts: &[Ghost]) -> Result<u64, MeetingError> {
+    // Until the last ghost has entered its cycle, the ghosts can only meet at
+    // one of the end points that ghost passes along the way.
+    let last = ghosts
+        .iter()
+        .max_by_key(|g| g.cycle_start)
+        .ok_or(MeetingError::NoGhosts)?;
+    let early_meeting = last
+        .lead_in_hits
+        .iter()
+        .copied()
+        .find(|&steps| ghosts.iter().all(|g| g.is_at_end(steps)));
+    if let Some(steps) = early_meeting {
+        return Ok(steps);
+    }
 
-    let mut candidate = max;
-    while candidate % min != 0 {
-        candidate += max;
+    // After that, every ghost is cycling, so each of them is at an end point
+    // exactly when the step count has one of a few remainders modulo its cycle
+    // length. Combining the ghosts one by one keeps the remainders at which all
+    // of them so far are at an end point together.
+    let latest_start = last.cycle_start;
+    let mut together = Schedule {
+        period: 1,
+        remainders: vec![0],
+    };
+    for ghost in ghosts {
+        together = together.combine(&Schedule::of(ghost))?;
+        if together.remainders.is_empty() {
+            return Err(MeetingError::Never);
+        }
     }
-    candidate
+
+    // The first meeting may lie before some ghosts have started cycling, in
+    // which case it doesn't count, and the next one in the period does.
+    let period = together.period as u64;
+    together
+        .remainders
+        .iter()
+        .map(|&remainder| {
+            let remainder = remainder as u64;
+            latest_start
+                .saturating_sub(remainder)
+                .div_ceil(period)
+                .checked_mul(period)
+                .and_then(|lift| lift.checked_add(remainder))
+                .ok_or(MeetingError::Overflow)
+        })
+        .try_fold(u64::MAX, |first, steps| Ok(first.min(steps?)))
 }
 
-struct Cursor<'m, I> {
-    instructions: I,
+/// The most remainders two schedules may be combined through, which bounds
+/// the work done for ghosts whose cycles pass many end points.
+const MAX_COMBINATIONS: usize = 1 << 20;
+
+/// The step counts at which one or more cycling ghosts are all at an end point:
+/// those leaving one of the remainders when divided by the period.
+struct Schedule {
+    period: i64,
+    /// Sorted and without duplicates.
+    remainders: Vec<i64>,
+}
+impl Schedule {
+    fn of(ghost: &Ghost) -> Schedule {
+        let period = ghost.cycle_length as i64;
+        let mut remainders: Vec<_> = ghost
+            .cycle_hits
+            .iter()
+            .map(|&hit| (ghost.cycle_start + hit) as i64 % period)
+            .collect();
+        remainders.sort_unstable();
+        Schedule { period, remainders }
+    }
+
+    /// Finds the step counts that are on both schedules. If there are none, the
+    /// period of the result is meaningless.
+    fn combine(&self, other: &Schedule) -> Result<Schedule, MeetingError> {
+        if self.remainders.len() * other.remainders.len() > MAX_COMBINATIONS {
+            return Err(MeetingError::TooManyCombinations);
+        }
+
+        let mut period = self.period;
+        let mut remainders = vec![];
+        for (&a, &b) in self.remainders.iter().cartesian_product(&other.remainders) {
+            match math::crt([(a, self.period), (b, other.period)]) {
+                Ok((remainder, lcm)) => {
+                    remainders.push(remainder);
+                    period = lcm;
+                }
+                // The ghosts are never at these end points together, but may
+                // be at others.
+                Err(CrtError::Contradiction) => {}
+                Err(CrtError::Overflow) => return Err(MeetingError::Overflow),
+            }
+        }
+        remainders.sort_unstable();
+        remainders.dedup();
+
+        Ok(Schedule { period, remainders })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MeetingError {
+    /// There are no labels ending in `A` for the ghosts to start at.
+    NoGhosts,
+    /// The ghosts are never at end points at the same time.
+    Never,
+    /// The ghosts only meet after more steps than can be counted.
+    Overflow,
+    /// The ghosts' cycles pass so many end points that there are too many
+    /// ways for them to line up to go through.
+    TooManyCombinations,
+}
+impl fmt::Display for MeetingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeetingError::NoGhosts => write!(f, "there are no starting points for the ghosts"),
+            MeetingError::Never => {
+                write!(f, "the ghosts are never at the end points at the same time")
+            }
+            MeetingError::Overflow => {
+                write!(f, "the ghosts meet after too many steps to count")
+            }
+            MeetingError::TooManyCombinations => write!(
+                f,
+                "the ghosts pass too many end points to work out when they meet"
+            ),
+        }
+    }
+}
+
+/// The step counts at which a ghost is at an end point.
+///
+/// Where a ghost goes next only depends on its position and where it is in the
+/// instructions, so once it finds itself in the same state again, it will keep
+/// repeating the steps in between forever.
+struct Ghost {
+    /// End points reached before entering the cycle.
+    lead_in_hits: Vec<u64>,
+    /// The number of steps after which the cycle is entered.
+    cycle_start: u64,
+    cycle_length: u64,
+    /// End points reached within the cycle, counted from its start.
+    cycle_hits: Vec<u64>,
+}
+impl Ghost {
+    fn track(mut cursor: Cursor) -> Ghost {
+        // The step at which each state was first visited.
+        let mut visited = vec![None; cursor.state_count()];
+        let mut hits = vec![];
+        let mut steps = 0;
+
+        let cycle_start = loop {
+            if let Some(first_visit) = visited[cursor.state()] {
+                break first_visit;
+            }
+            visited[cursor.state()] = Some(steps);
+            if cursor.map.label(cursor.position).ends_with('Z') {
+                hits.push(steps);
+            }
+            cursor.step();
+            steps += 1;
+        };
+
+        let cycle_hits = hits
+            .split_off(hits.partition_point(|&h| h < cycle_start))
+            .into_iter()
+            .map(|h| h - cycle_start)
+            .collect();
+
+        Ghost {
+            lead_in_hits: hits,
+            cycle_start,
+            cycle_length: steps - cycle_start,
+            cycle_hits,
+        }
+    }
+
+    fn is_at_end(&self, steps: u64) -> bool {
+        match steps.checked_sub(self.cycle_start) {
+            None => self.lead_in_hits.contains(&steps),
+            Some(in_cycle) => self
+                .cycle_hits
+                .binary_search(&(in_cycle % self.cycle_length))
+                .is_ok(),
+        }
+    }
+}
+
+struct Cursor<'m> {
+    instructions: &'m [Instruction],
     map: &'m Map,
-    position: Label,
+    position: Node,
+    /// Index of the next instruction to follow.
+    instruction_idx: usize,
 }
-impl<'m, I: Iterator<Item = Instruction>> Cursor<'m, I> {
-    fn new(start: Label, map: &'m Map, instructions: I) -> Self {
+impl<'m> Cursor<'m> {
+    fn new(start: Node, map: &'m Map, instructions: &'m [Instruction]) -> Self {
         Self {
             instructions,
             map,
             position: start,
+            instruction_idx: 0,
         }
     }
 
-    fn walk_to<P>(&mut self, end_predicate: P) -> u64
+    /// Identifies the current position together with the next instruction, as a
+    /// number below [`Cursor::state_count`].
+    fn state(&self) -> usize {
+        self.position as usize * self.instructions.len() + self.instruction_idx
+    }
+
+    fn state_count(&self) -> usize {
+        self.map.labels.len() * self.instructions.len()
+    }
+
+    /// Walks until the end predicate is satisfied, returning the number of steps
+    /// taken. Fails if the walk starts going in circles without ever getting
+    /// there.
+    fn walk_to<P>(&mut self, end_predicate: P) -> Result<u64, WalkError>
     where
-        P: Fn(Label) -> bool,
+        P: Fn(Node) -> bool,
     {
+        let mut visited = vec![false; self.state_count()];
         let mut steps = 0;
         while !end_predicate(self.position) {
+            if std::mem::replace(&mut visited[self.state()], true) {
+                return Err(WalkError::Unreachable {
+                    from: self.map.label(self.position),
+                    steps,
+                });
+            }
             self.step();
             steps += 1;
         }
 
-        steps
+        Ok(steps)
+    }
+
+    /// Walks until the walk starts repeating itself, returning the junctions
+    /// passed in the repeating part along with the way taken at each of them.
+    fn find_cycle(&mut self) -> Vec<(Node, Instruction)> {
+        let mut visited = vec![false; self.state_count()];
+        while !std::mem::replace(&mut visited[self.state()], true) {
+            self.step();
+        }
+
+        let cycle_start = self.state();
+        let mut edges = vec![];
+        loop {
+            edges.push((self.position, self.instructions[self.instruction_idx]));
+            self.step();
+            if self.state() == cycle_start {
+                return edges;
+            }
+        }
     }
 
     fn step(&mut self) {
-        let instruction = self.instructions.next().unwrap();
-        let next_position = self.map.junction(&self.position).follow(instruction);
-        self.position = next_position;
+        let instruction = self.instructions[self.instruction_idx];
+        self.instruction_idx = (self.instruction_idx + 1) % self.instructions.len();
+        self.position = self.map.junction(self.position)[instruction as usize];
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum WalkError {
+    /// The walk ended up in a loop which doesn't pass any end point.
+    Unreachable { from: Label, steps: u64 },
+}
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalkError::Unreachable { from, steps } => write!(
+                f,
+                "the end is unreachable, the walk started repeating itself at {from} after {steps} steps"
+            ),
+        }
     }
 }
 
+/// Index of a node in the [`Map`].
+type Node = u16;
+
 pub struct Map {
-    connections: FxHashMap<Label, Junction>,
+    /// The label of every node, indexed by node.
+    labels: Vec<Label>,
+    /// The nodes to the left and right of every node, indexed by node.
+    junctions: Vec<[Node; 2]>,
 }
 impl Map {
-    fn junction(&self, start: &Label) -> &Junction {
-        &self.connections[start]
+    /// Numbers all labels, so the network can be navigated without hashing.
+    /// Every label must have exactly one junction, and there may be no more
+    /// labels than can be numbered.
+    fn new(definitions: &[Junction]) -> Result<Map, MapError> {
+        let mut nodes = FxHashMap::default();
+        let labels: Vec<_> = definitions.iter().map(|&(label, _)| label).collect();
+        for (definition, &label) in labels.iter().enumerate() {
+            let node = Node::try_from(definition).map_err(|_| MapError::TooManyLabels)?;
+            if nodes.insert(label, node).is_some() {
+                return Err(MapError::Duplicate { definition });
+            }
+        }
+
+        let node = |label| nodes.get(&label).copied().ok_or(MapError::Dangling(label));
+        let junctions = definitions
+            .iter()
+            .map(|&(_, [left, right])| Ok([node(left)?, node(right)?]))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Map { labels, junctions })
+    }
+
+    fn junction(&self, node: Node) -> [Node; 2] {
+        self.junctions[node as usize]
+    }
+
+    fn label(&self, node: Node) -> Label {
+        self.labels[node as usize]
+    }
+
+    fn find(&self, label: Label) -> Option<Node> {
+        let node = self.labels.iter().position(|&l| l == label)?;
+        Some(node as Node)
     }
 
-    fn starting_points(&self) -> impl Iterator<Item = Label> + '_ {
-        self.connections
-            .keys()
-            .copied()
-            .filter(|k| k.ends_with('A'))
+    fn starting_points(&self) -> impl Iterator<Item = Node> + '_ {
+        (0..self.labels.len() as Node).filter(|&n| self.label(n).ends_with('A'))
+    }
+
+    /// Writes the network as a Graphviz graph, with starting points in green and
+    /// end points in red. If instructions are given, the cycle every ghost ends
+    /// up in is drawn in a colour of its own.
+    pub fn write_dot<W: Write>(
+        &self,
+        mut out: W,
+        instructions: Option<&[Instruction]>,
+    ) -> io::Result<()> {
+        const CYCLE_COLOURS: [&str; 6] = ["blue", "orange", "purple", "cyan4", "brown", "magenta"];
+
+        let mut edge_colours = FxHashMap::default();
+        if let Some(instrs) = instructions {
+            for (start, colour) in self.starting_points().zip(CYCLE_COLOURS.iter().cycle()) {
+                for (node, instruction) in Cursor::new(start, self, instrs).find_cycle() {
+                    edge_colours
+                        .entry((node, instruction as usize))
+                        .or_insert(*colour);
+                }
+            }
+        }
+
+        writeln!(out, "digraph network {{")?;
+        for node in 0..self.labels.len() as Node {
+            let label = self.label(node);
+            if label.ends_with('A') {
+                writeln!(out, "    \"{label}\" [style=filled, fillcolor=palegreen];")?;
+            } else if label.ends_with('Z') {
+                writeln!(out, "    \"{label}\" [style=filled, fillcolor=lightcoral];")?;
+            } else {
+                writeln!(out, "    \"{label}\";")?;
+            }
+        }
+        for (node, targets) in self.junctions.iter().enumerate() {
+            let node = node as Node;
+            for (side, name) in ["L", "R"].into_iter().enumerate() {
+                write!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label={name}",
+                    self.label(node),
+                    self.label(targets[side])
+                )?;
+                if let Some(colour) = edge_colours.get(&(node, side)) {
+                    write!(out, ", color={colour}, penwidth=2")?;
+                }
+                writeln!(out, "];")?;
+            }
+        }
+        writeln!(out, "}}")
     }
 }
 
@@ -102,37 +442,83 @@ impl Label {
         self.0[2] == char
     }
 }
-
-struct Junction {
-    left: Label,
-    right: Label,
-}
-impl Junction {
-    fn new(left: Label, right: Label) -> Self {
-        Self { left, right }
-    }
-
-    fn follow(&self, instruction: Instruction) -> Label {
-        match instruction {
-            Instruction::Left => self.left,
-            Instruction::Right => self.right,
-        }
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|c| write!(f, "{c}"))
     }
 }
 
+/// Which way to go at a junction. The discriminant indexes the junction table.
 #[derive(Clone, Copy)]
 pub enum Instruction {
-    Left,
-    Right,
+    Left = 0,
+    Right = 1,
 }
 
 impl DayInput for (Vec<Instruction>, Map) {
-    fn load(input: &'static str) -> Self {
-        common::parse(instructions_map, input)
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        let (instructions, definitions) = common::parse(instructions_map, input)?;
+        let junctions: Vec<_> = definitions.iter().map(|&(_, j)| j).collect();
+
+        let map = Map::new(&junctions).map_err(|e| match e {
+            MapError::Duplicate { definition } => {
+                let (line, (label, _)) = definitions[definition];
+                InputError::at(
+                    input,
+                    line,
+                    format!("a label without a junction, but {label} already has one"),
+                )
+            }
+            MapError::TooManyLabels => {
+                let (line, _) = definitions[Node::MAX as usize + 1];
+                InputError::at(
+                    input,
+                    line,
+                    format!("at most {} junctions", Node::MAX as usize + 1),
+                )
+            }
+            // A walk arriving at a label without a junction would have nowhere to go.
+            MapError::Dangling(label) => InputError::at(
+                input,
+                first_reference(input, label),
+                format!("a label with a junction, but {label} has none"),
+            ),
+        })?;
+        Ok((instructions, map))
     }
 }
 
-fn instructions_map(i: &str) -> IResult<&str, (Vec<Instruction>, Map)> {
+/// Why a [`Map`] could not be built from a list of junctions.
+#[derive(Debug, PartialEq, Eq)]
+enum MapError {
+    /// A label has more than one junction; the definition at this index is the
+    /// first one to repeat a label.
+    Duplicate { definition: usize },
+    /// There are more labels than fit in a [`Node`].
+    TooManyLabels,
+    /// Junctions lead to this label, but it has no junction of its own.
+    Dangling(Label),
+}
+
+/// Finds where a label is first used as the destination of a junction.
+fn first_reference(input: &str, label: Label) -> &str {
+    let label = label.to_string();
+    input
+        .lines()
+        .find_map(|line| {
+            let (_, destinations) = line.split_once(" = ")?;
+            let offset = destinations.find(&label)?;
+            Some(&destinations[offset..])
+        })
+        .unwrap_or(input)
+}
+
+/// A label along with the labels to its left and right.
+type Junction = (Label, [Label; 2]);
+/// A junction along with the line of input it was parsed from.
+type Definition<'i> = (&'i str, Junction);
+
+fn instructions_map(i: &str) -> IResult<&str, (Vec<Instruction>, Vec<Definition<'_>>)> {
     pair(terminated(instructions, count(line_ending, 2)), junctions)(i)
 }
 
@@ -144,27 +530,20 @@ fn instructions(i: &str) -> IResult<&str, Vec<Instruction>> {
     }))(i)
 }
 
-fn junctions(i: &str) -> IResult<&str, Map> {
-    map(
-        many1(pair(
-            terminated(label, tag(" = ")),
-            terminated(junction, line_ending),
-        )),
-        |t: Vec<(Label, Junction)>| {
-            let mut connections = FxHashMap::default();
-            connections.extend(t);
-            Map { connections }
-        },
-    )(i)
+fn junctions(i: &str) -> IResult<&str, Vec<Definition<'_>>> {
+    many1(consumed(pair(
+        terminated(label, tag(" = ")),
+        cut(terminated(junction, line_ending)),
+    )))(i)
 }
 
-fn junction(i: &str) -> IResult<&str, Junction> {
+fn junction(i: &str) -> IResult<&str, [Label; 2]> {
     map(
         pair(
             delimited(tag("("), label, tag(", ")),
             terminated(label, tag(")")),
         ),
-        |(l, r)| Junction::new(l, r),
+        |(l, r)| [l, r],
     )(i)
 }
 
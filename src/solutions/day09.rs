@@ -1,6 +1,16 @@
-use nom::{bytes::complete::tag, combinator::map, multi::separated_list1, IResult};
+use nom::{
+    bytes::complete::tag,
+    combinator::{cut, map},
+    multi::separated_list1,
+    IResult,
+};
 
-use crate::{common, input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day09;
 impl Day for Day09 {
@@ -8,12 +18,12 @@ impl Day for Day09 {
 
     const DAY_NO: usize = 9;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        input.iter().map(|s| s.append()).sum::<i64>() as u64
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.iter().map(|s| s.append()).sum::<i64>().into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        input.iter().map(|s| s.prepend()).sum::<i64>() as u64
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.iter().map(|s| s.prepend()).sum::<i64>().into())
     }
 }
 
@@ -62,11 +72,11 @@ impl Sequence {
 }
 
 impl DayInput for Sequence {
-    fn load(input: &'static str) -> Self {
+    fn load(input: &'static str) -> Result<Self, InputError> {
         common::parse(sequence, input)
     }
 }
 
 fn sequence(i: &str) -> IResult<&str, Sequence> {
-    map(separated_list1(tag(" "), common::i64), Sequence::new)(i)
+    map(separated_list1(tag(" "), cut(common::i64)), Sequence::new)(i)
 }
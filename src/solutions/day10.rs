@@ -1,31 +1,35 @@
-use std::ops::Add;
+use std::fmt;
 
-use nom::{
-    character::complete::{anychar, line_ending},
-    combinator::{map, map_opt},
-    multi::many1,
-    sequence::terminated,
-    IResult,
+use crate::{
+    answer::{Answer, SolveError},
+    common::{
+        geom::{self, Direction, Point},
+        grid::Grid,
+    },
+    input::{DayInput, InputError},
+    Day,
 };
 
-use crate::{common, input::DayInput, Day};
-
 pub struct Day10;
 impl Day for Day10 {
     type Input = Map;
 
     const DAY_NO: usize = 10;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
         let mut cursor = Cursor::new(input, Direction::Down);
-        let props = cursor.find_length().unwrap();
-        props.midpoint
+        let props = cursor
+            .find_length()
+            .ok_or_else(|| SolveError::new("there is no loop through the start tile"))?;
+        Ok(props.midpoint.into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
         let mut cursor = Cursor::new(input, Direction::Down);
-        let props = cursor.find_length().unwrap();
-        props.enclosed_area
+        let props = cursor
+            .find_length()
+            .ok_or_else(|| SolveError::new("there is no loop through the start tile"))?;
+        Ok(props.enclosed_area.into())
     }
 }
 
@@ -68,8 +72,9 @@ impl<'m> Cursor<'m> {
         // The midpoint lies exactly halfway along the pipeline.
         let midpoint = length / 2;
         // The shoelace formula produces the area of a shape times two.
-        // Divide by two to get the actual area.
-        let internal_area = self.shoelace_area / 2;
+        // Divide by two to get the actual area. The sign depends on whether we
+        // went around the loop clockwise or anticlockwise, so drop it.
+        let internal_area = self.shoelace_area.abs() / 2;
         // The area now calculated represents the area covered by a pipe network
         // where pipes cross through the centre of each tile. In other words,
         // if the pipe makes the simplest loop possible, using only four corner
@@ -123,7 +128,7 @@ impl<'m> Cursor<'m> {
             Some(new_orientation) => {
                 // Calculate the area the shape the pipeline is drawing using the
                 // shoelace formula: https://en.wikipedia.org/wiki/Shoelace_formula
-                self.shoelace_area += self.position.shoelace(next_position);
+                self.shoelace_area += geom::shoelace(self.position, next_position);
                 self.orientation = new_orientation;
                 self.position = next_position;
                 self.step_count += 1;
@@ -134,84 +139,105 @@ impl<'m> Cursor<'m> {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+pub struct Map {
+    start: Point,
+    tiles: Grid<Tile>,
 }
-impl Direction {
-    fn opposite(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Down,
-            Direction::Right => Direction::Left,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-        }
+impl Map {
+    fn tile(&self, point: Point) -> Tile {
+        self.tiles.get(point).copied().unwrap_or(Tile::Empty)
     }
-}
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-impl Point {
-    pub fn new(x: usize, y: usize) -> Self {
-        Self { x, y }
-    }
+    /// Works out which tiles are part of the main loop and which tiles it
+    /// encloses, so they can be drawn. Returns `None` if there is no loop
+    /// through the start tile.
+    pub fn visualize(&self) -> Option<Visualization> {
+        let mut roles = self.tiles.map(|tile| match tile {
+            Tile::Pipe(pipe) => Role::Junk(*pipe),
+            _ => Role::Outside,
+        });
 
-    /// Calculates a single step in the shoelace formula.
-    fn shoelace(&self, next: Point) -> i64 {
-        let (x1, y1) = (self.x as i64, self.y as i64);
-        let (x2, y2) = (next.x as i64, next.y as i64);
+        let mut cursor = Cursor::new(self, Direction::Down);
+        while cursor.position != self.start || cursor.step_count == 0 {
+            if !cursor.step() {
+                return None;
+            }
+            roles[cursor.position] = match self.tile(cursor.position) {
+                Tile::Pipe(pipe) => Role::Loop(pipe),
+                // The start tile connects to where the walk set off to, and to
+                // where it came back from.
+                _ => Role::Loop(Pipe::connecting([
+                    Direction::Down,
+                    cursor.orientation.opposite(),
+                ])?),
+            };
+        }
 
-        (y1 + y2) * (x1 - x2)
+        // Walking along a row, every loop section that connects upwards is a
+        // crossing from outside the loop to inside of it, or the other way
+        // around.
+        let mut inside = false;
+        for point in self.tiles.points() {
+            if point.x == 0 {
+                inside = false;
+            }
+            match roles[point] {
+                Role::Loop(pipe) => inside ^= pipe.corners().contains(&Direction::Up),
+                _ if inside => roles[point] = Role::Enclosed,
+                _ => {}
+            }
+        }
+
+        Some(Visualization {
+            start: self.start,
+            roles,
+        })
     }
 }
-impl Add<Direction> for Point {
-    type Output = Point;
 
-    fn add(self, rhs: Direction) -> Self::Output {
-        match rhs {
-            Direction::Up => Self::new(self.x, self.y - 1),
-            Direction::Right => Self::new(self.x + 1, self.y),
-            Direction::Down => Self::new(self.x, self.y + 1),
-            Direction::Left => Self::new(self.x - 1, self.y),
-        }
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
     }
 }
 
-pub struct Map {
+/// The map drawn for a terminal, with the main loop highlighted, the tiles it
+/// encloses marked, and all other pipes dimmed.
+pub struct Visualization {
     start: Point,
-    width: usize,
-    tiles: Vec<Tile>,
+    roles: Grid<Role>,
 }
-impl Map {
-    fn from_lines(lines: Vec<Vec<Tile>>) -> Self {
-        let height = lines.len();
-        let width = lines[0].len();
-        let mut start = Point::new(0, 0);
-        let mut tiles = Vec::with_capacity(height * width);
-        for (y, line) in lines.into_iter().enumerate() {
-            for (x, tile) in line.into_iter().enumerate() {
-                tiles.push(tile);
-                if tile == Tile::Start {
-                    start = Point::new(x, y);
-                }
-            }
-        }
 
-        Self {
-            start,
-            width,
-            tiles,
-        }
-    }
+#[derive(Clone, Copy)]
+enum Role {
+    Loop(Pipe),
+    /// A pipe which isn't part of the main loop.
+    Junk(Pipe),
+    Enclosed,
+    Outside,
+}
 
-    fn tile(&self, point: Point) -> Tile {
-        self.tiles[point.y * self.width + point.x]
+impl fmt::Display for Visualization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const START: &str = "\x1b[1;31m";
+        const LOOP: &str = "\x1b[1;33m";
+        const ENCLOSED: &str = "\x1b[32m";
+        const JUNK: &str = "\x1b[2m";
+        const RESET: &str = "\x1b[0m";
+
+        for (point, role) in self.roles.iter() {
+            if point.x == 0 && point.y > 0 {
+                writeln!(f)?;
+            }
+            match role {
+                Role::Loop(pipe) if point == self.start => write!(f, "{START}{pipe}{RESET}")?,
+                Role::Loop(pipe) => write!(f, "{LOOP}{pipe}{RESET}")?,
+                Role::Junk(pipe) => write!(f, "{JUNK}{pipe}{RESET}")?,
+                Role::Enclosed => write!(f, "{ENCLOSED}•{RESET}")?,
+                Role::Outside => write!(f, " ")?,
+            }
+        }
+        Ok(())
     }
 }
 
@@ -238,6 +264,16 @@ impl Tile {
     }
 }
 
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tile::Start => write!(f, "S"),
+            Tile::Empty => write!(f, "."),
+            Tile::Pipe(pipe) => write!(f, "{pipe}"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Pipe {
     UD, // up <> down
@@ -248,6 +284,15 @@ pub enum Pipe {
     LU, // left <> up
 }
 impl Pipe {
+    const ALL: [Pipe; 6] = [Pipe::UD, Pipe::LR, Pipe::UR, Pipe::RD, Pipe::DL, Pipe::LU];
+
+    /// Finds the pipe that connects both directions, in either order.
+    fn connecting([a, b]: [Direction; 2]) -> Option<Pipe> {
+        Pipe::ALL
+            .into_iter()
+            .find(|pipe| pipe.corners() == [a, b] || pipe.corners() == [b, a])
+    }
+
     fn corners(&self) -> [Direction; 2] {
         match self {
             Pipe::UD => [Direction::Up, Direction::Down],
@@ -260,26 +305,39 @@ impl Pipe {
     }
 }
 
-impl DayInput for Map {
-    fn load(input: &'static str) -> Self {
-        common::parse(tiles, input)
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let char = match self {
+            Pipe::UD => '│',
+            Pipe::LR => '─',
+            Pipe::UR => '└',
+            Pipe::RD => '┌',
+            Pipe::DL => '┐',
+            Pipe::LU => '┘',
+        };
+        write!(f, "{char}")
     }
 }
 
-fn tiles(i: &str) -> IResult<&str, Map> {
-    map(many1(terminated(many1(tile), line_ending)), Map::from_lines)(i)
-}
+impl DayInput for Map {
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        let tiles = Grid::parse(input, |c| match c {
+            'S' => Some(Tile::Start),
+            '.' => Some(Tile::Empty),
+            '|' => Some(Tile::Pipe(Pipe::UD)),
+            '-' => Some(Tile::Pipe(Pipe::LR)),
+            'L' => Some(Tile::Pipe(Pipe::UR)),
+            'F' => Some(Tile::Pipe(Pipe::RD)),
+            '7' => Some(Tile::Pipe(Pipe::DL)),
+            'J' => Some(Tile::Pipe(Pipe::LU)),
+            _ => None,
+        })?;
+        let start = tiles
+            .iter()
+            .find(|(_, &tile)| tile == Tile::Start)
+            .map(|(p, _)| p)
+            .ok_or_else(|| InputError::at(input, &input[input.len()..], "a start tile"))?;
 
-fn tile(i: &str) -> IResult<&str, Tile> {
-    map_opt(anychar, |c| match c {
-        'S' => Some(Tile::Start),
-        '.' => Some(Tile::Empty),
-        '|' => Some(Tile::Pipe(Pipe::UD)),
-        '-' => Some(Tile::Pipe(Pipe::LR)),
-        'L' => Some(Tile::Pipe(Pipe::UR)),
-        'F' => Some(Tile::Pipe(Pipe::RD)),
-        '7' => Some(Tile::Pipe(Pipe::DL)),
-        'J' => Some(Tile::Pipe(Pipe::LU)),
-        _ => None,
-    })(i)
+        Ok(Map { start, tiles })
+    }
 }
@@ -1,8 +1,14 @@
-use std::ops::{Add, Mul};
-
 use itertools::Itertools;
 
-use crate::{input::DayInput, Day};
+use crate::{
+    answer::{Answer, SolveError},
+    common::{
+        geom::{Point, Vector},
+        grid::Grid,
+    },
+    input::{DayInput, InputError},
+    Day,
+};
 
 pub struct Day11;
 impl Day for Day11 {
@@ -10,158 +16,86 @@ impl Day for Day11 {
 
     const DAY_NO: usize = 11;
 
-    fn solve_challenge_1(input: &Self::Input) -> u64 {
-        const EXPANSION_COEFFICIENT: u64 = 1;
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        const EXPANSION_COEFFICIENT: i64 = 1;
 
-        input
+        Ok(input
             .iter()
             .copied()
             .tuple_combinations()
             .map(|(a, b)| a.measure_distance(&b, EXPANSION_COEFFICIENT))
-            .sum()
+            .sum::<u64>()
+            .into())
     }
 
-    fn solve_challenge_2(input: &Self::Input) -> u64 {
-        const EXPANSION_COEFFICIENT: u64 = 999999;
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        const EXPANSION_COEFFICIENT: i64 = 999999;
 
-        input
+        Ok(input
             .iter()
             .copied()
             .tuple_combinations()
             .map(|(a, b)| a.measure_distance(&b, EXPANSION_COEFFICIENT))
-            .sum()
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: u64,
-    y: u64,
-}
-impl Point {
-    fn new(x: u64, y: u64) -> Self {
-        Self { x, y }
-    }
-
-    fn taxicab_distance(&self, other: Point) -> u64 {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-    }
-}
-impl Mul<u64> for Point {
-    type Output = Self;
-
-    fn mul(self, rhs: u64) -> Self::Output {
-        Self::new(self.x * rhs, self.y * rhs)
-    }
-}
-impl Add for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Point::new(self.x + rhs.x, self.y + rhs.y)
+            .sum::<u64>()
+            .into())
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct Planet {
     position: Point,
-    expansion_correction: Point,
+    expansion_correction: Vector,
 }
 impl Planet {
-    fn new(position: Point, expansion_correction: Point) -> Planet {
+    fn new(position: Point, expansion_correction: Vector) -> Planet {
         Planet {
             position,
             expansion_correction,
         }
     }
 
-    fn measure_distance(&self, other: &Planet, expansion_coefficient: u64) -> u64 {
+    fn measure_distance(&self, other: &Planet, expansion_coefficient: i64) -> u64 {
         self.corrected_position(expansion_coefficient)
             .taxicab_distance(other.corrected_position(expansion_coefficient))
     }
 
-    fn corrected_position(&self, expansion_coefficient: u64) -> Point {
-        let expansion_transform = self.expansion_correction * expansion_coefficient;
-        self.position + expansion_transform
+    fn corrected_position(&self, expansion_coefficient: i64) -> Point {
+        self.position + self.expansion_correction * expansion_coefficient
     }
 }
 
 impl DayInput for Vec<Planet> {
-    fn load(input: &'static str) -> Self {
-        let mut density = CosmicDensity::from_map(input);
-        let mut planets = Vec::with_capacity(256);
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        let image = Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
 
-        for (y, line) in input.lines().enumerate() {
-            density.correct_for_y(y);
-            density.reset_x_correction();
+        let x_corrections = count_empty(image.columns().map(|mut c| !c.any(|&g| g)));
+        let y_corrections = count_empty(image.rows().map(|r| !r.contains(&true)));
 
-            for (x, ch) in line.chars().enumerate() {
-                density.correct_for_x(x);
-
-                if ch == '#' {
-                    planets.push(Planet::new(
-                        Point::new(x as u64, y as u64),
-                        density.current_correction_factor(),
-                    ));
-                }
-            }
-        }
-
-        planets
+        let planets = image
+            .iter()
+            .filter(|(_, &galaxy)| galaxy)
+            .map(|(p, _)| {
+                let correction =
+                    Vector::new(x_corrections[p.x as usize], y_corrections[p.y as usize]);
+                Planet::new(p, correction)
+            })
+            .collect();
+
+        Ok(planets)
     }
 }
 
-struct CosmicDensity {
-    empty_x: Vec<bool>,
-    empty_y: Vec<bool>,
-    x_correction: u64,
-    y_correction: u64,
-}
-impl CosmicDensity {
-    fn from_map(map: &str) -> CosmicDensity {
-        let mut empty_x = Vec::with_capacity(140);
-        let mut empty_y = Vec::with_capacity(140);
-        for (y, line) in map.lines().enumerate() {
-            // Each newly encountered row starts out empty unless proven otherwise.
-            empty_y.push(true);
-            for (x, ch) in line.chars().enumerate() {
-                if y == 0 {
-                    // We only need to discover new columns when iterating through the first row.
-                    empty_x.push(true);
-                }
-
-                if ch == '#' {
-                    empty_x[x] = false;
-                    empty_y[y] = false;
-                }
-            }
-        }
-
-        Self {
-            empty_x,
-            empty_y,
-            x_correction: 0,
-            y_correction: 0,
-        }
-    }
-
-    fn current_correction_factor(&self) -> Point {
-        Point::new(self.x_correction, self.y_correction)
-    }
-
-    fn reset_x_correction(&mut self) {
-        self.x_correction = 0;
-    }
-
-    fn correct_for_y(&mut self, y: usize) {
-        if self.empty_y[y] {
-            self.y_correction += 1;
-        }
-    }
-
-    fn correct_for_x(&mut self, x: usize) {
-        if self.empty_x[x] {
-            self.x_correction += 1;
-        }
-    }
+/// For every row or column, counts the number of empty rows or columns up to
+/// and including it.
+fn count_empty(empty: impl Iterator<Item = bool>) -> Vec<i64> {
+    empty
+        .scan(0, |count, empty| {
+            *count += i64::from(empty);
+            Some(*count)
+        })
+        .collect()
 }
@@ -0,0 +1,139 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::anychar,
+    combinator::{cut, map, map_opt},
+    multi::{many1, separated_list1},
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::{
+    answer::{Answer, SolveError},
+    common,
+    input::{DayInput, InputError},
+    Day,
+};
+
+pub struct Day12;
+impl Day for Day12 {
+    type Input = Vec<Row>;
+
+    const DAY_NO: usize = 12;
+
+    fn solve_challenge_1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.iter().map(Row::arrangements).sum::<u64>().into())
+    }
+
+    fn solve_challenge_2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input
+            .iter()
+            .map(|r| r.unfold(5).arrangements())
+            .sum::<u64>()
+            .into())
+    }
+}
+
+pub struct Row {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+impl Row {
+    /// Repeats the row a number of times, joining the copies of the springs with
+    /// an unknown spring.
+    fn unfold(&self, times: usize) -> Row {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * times);
+        for copy in 0..times {
+            if copy > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Row {
+            springs,
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    /// Counts the ways in which the unknown springs can be filled in to match the
+    /// groups of damaged springs.
+    fn arrangements(&self) -> u64 {
+        let springs = &self.springs;
+        let groups = &self.groups;
+        let width = groups.len() + 1;
+
+        // counts[s * width + g] holds the number of arrangements for the springs
+        // starting at `s`, given the groups starting at `g`. It is filled in from
+        // the back, so each entry can be derived from entries that come after it.
+        let mut counts = vec![0u64; (springs.len() + 1) * width];
+        counts[springs.len() * width + groups.len()] = 1;
+
+        for s in (0..springs.len()).rev() {
+            for g in 0..=groups.len() {
+                let mut count = 0;
+
+                if springs[s] != Spring::Damaged {
+                    // This spring is operational, so it can simply be skipped.
+                    count += counts[(s + 1) * width + g];
+                }
+
+                if springs[s] != Spring::Operational && g < groups.len() {
+                    // This spring is damaged, so it must start the next group.
+                    let end = s + groups[g];
+                    if Self::fits_group(springs, s, end) {
+                        // Skip the spring after the group too, it must be operational.
+                        let next = (end + 1).min(springs.len());
+                        count += counts[next * width + g + 1];
+                    }
+                }
+
+                counts[s * width + g] = count;
+            }
+        }
+
+        counts[0]
+    }
+
+    /// Checks whether `start..end` can be a group of damaged springs which is not
+    /// immediately followed by another damaged spring.
+    fn fits_group(springs: &[Spring], start: usize, end: usize) -> bool {
+        end <= springs.len()
+            && springs[start..end]
+                .iter()
+                .all(|&s| s != Spring::Operational)
+            && springs.get(end) != Some(&Spring::Damaged)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl DayInput for Row {
+    fn load(input: &'static str) -> Result<Self, InputError> {
+        common::parse(row, input)
+    }
+}
+
+fn row(i: &str) -> IResult<&str, Row> {
+    map(
+        separated_pair(
+            many1(spring),
+            tag(" "),
+            separated_list1(tag(","), cut(map(common::u32, |n| n as usize))),
+        ),
+        |(springs, groups)| Row { springs, groups },
+    )(i)
+}
+
+fn spring(i: &str) -> IResult<&str, Spring> {
+    map_opt(anychar, |c| match c {
+        '.' => Some(Spring::Operational),
+        '#' => Some(Spring::Damaged),
+        '?' => Some(Spring::Unknown),
+        _ => None,
+    })(i)
+}
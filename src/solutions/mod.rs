@@ -1,3 +1,5 @@
+use crate::registry::Registry;
+
 pub use day01::Day01;
 pub use day02::Day02;
 pub use day03::Day03;
@@ -23,3 +25,8 @@ mod day09;
 mod day10;
 mod day11;
 mod day12;
+
+/// Every implemented day. Adding a new day only requires adding it here.
+pub static DAYS: Registry = Registry::new(&[
+    &Day01, &Day02, &Day03, &Day04, &Day05, &Day06, &Day07, &Day08, &Day09, &Day10, &Day11, &Day12,
+]);